@@ -0,0 +1,78 @@
+// Generates the list of built-in chapters from the partNN.rs files in src/, so
+// adding a chapter only means adding its file. Each file must define a unit
+// struct named after it (part14.rs -> `pub struct Part14;`) that implements
+// `chapter::Chapter`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Chapters keep the book's examples as they are written: variables that are
+// never read, functions that are never called, and the long-hand version of
+// code before the idiomatic one. The lints that flag exactly those are
+// allowed for the chapter modules only.
+const CHAPTER_LINTS: &str = "\
+#[allow(dead_code, unused_imports, unused_variables, unused_mut, unreachable_code)]
+#[allow(
+    clippy::manual_map,
+    clippy::no_effect,
+    clippy::ptr_arg,
+    clippy::question_mark,
+    clippy::single_match,
+    clippy::unnecessary_literal_unwrap,
+    clippy::unusual_byte_groupings,
+    clippy::useless_vec,
+    clippy::vec_init_then_push
+)]
+";
+
+fn main() {
+    let src_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let mut parts: Vec<String> = fs::read_dir(&src_dir)
+        .expect("src directory should be readable")
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| name.strip_suffix(".rs").map(str::to_string))
+        .filter(|stem| is_chapter_file(stem))
+        .collect();
+    parts.sort();
+
+    let mut generated = String::new();
+    for part in &parts {
+        let path = src_dir.join(format!("{}.rs", part));
+        generated.push_str(&format!(
+            "#[path = {:?}]\n{}pub mod {};\n",
+            path.display().to_string(),
+            CHAPTER_LINTS,
+            part
+        ));
+    }
+
+    generated.push_str("\nfn builtin_chapters() -> Vec<Box<dyn chapter::Chapter>> {\n    vec![\n");
+    for part in &parts {
+        generated.push_str(&format!(
+            "        Box::new({}::{}),\n",
+            part,
+            type_name(part)
+        ));
+    }
+    generated.push_str("    ]\n}\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("chapters.rs");
+    fs::write(out_path, generated).expect("generated chapter list should be writable");
+}
+
+// part01, part02, ... part14
+fn is_chapter_file(stem: &str) -> bool {
+    match stem.strip_prefix("part") {
+        Some(number) => !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+// part07 -> Part07
+fn type_name(part: &str) -> String {
+    format!("Part{}", &part["part".len()..])
+}
@@ -0,0 +1,194 @@
+//! Counting heap allocations, to see what moves and clones do to memory.
+//! Nothing is counted unless [`Tracer`] is the global allocator, which the
+//! binary sets up when built with `--features alloc-trace`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Sub;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What was asked of the allocator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub allocations: u64,
+    /// Blocks grown or shrunk in place or moved, as `Vec::push` does.
+    pub reallocations: u64,
+    pub deallocations: u64,
+    pub bytes_allocated: u64,
+    pub bytes_freed: u64,
+}
+
+impl Counts {
+    const ZERO: Counts = Counts {
+        allocations: 0,
+        reallocations: 0,
+        deallocations: 0,
+        bytes_allocated: 0,
+        bytes_freed: 0,
+    };
+
+    /// Whether the heap was not touched at all.
+    pub fn is_zero(&self) -> bool {
+        *self == Counts::ZERO
+    }
+}
+
+impl Sub for Counts {
+    type Output = Counts;
+
+    fn sub(self, earlier: Counts) -> Counts {
+        Counts {
+            allocations: self.allocations - earlier.allocations,
+            reallocations: self.reallocations - earlier.reallocations,
+            deallocations: self.deallocations - earlier.deallocations,
+            bytes_allocated: self.bytes_allocated - earlier.bytes_allocated,
+            bytes_freed: self.bytes_freed - earlier.bytes_freed,
+        }
+    }
+}
+
+thread_local! {
+    // Per thread, so that tests running side by side do not count each
+    // other's allocations. A const `Cell` of a `Copy` type needs no
+    // allocation of its own, which matters inside an allocator.
+    static COUNTS: Cell<Counts> = const { Cell::new(Counts::ZERO) };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+fn record(update: impl FnOnce(&mut Counts)) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    // Fails only while the thread is being torn down; those go uncounted.
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        update(&mut current);
+        counts.set(current);
+    });
+}
+
+/// The system allocator, counting every call on the current thread.
+pub struct Tracer;
+
+unsafe impl GlobalAlloc for Tracer {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|counts| {
+            counts.allocations += 1;
+            counts.bytes_allocated += layout.size() as u64;
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(|counts| {
+            counts.allocations += 1;
+            counts.bytes_allocated += layout.size() as u64;
+        });
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|counts| {
+            counts.reallocations += 1;
+            counts.bytes_allocated += new_size as u64;
+            counts.bytes_freed += layout.size() as u64;
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|counts| {
+            counts.deallocations += 1;
+            counts.bytes_freed += layout.size() as u64;
+        });
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Whether [`Tracer`] is the global allocator, so that counts mean anything.
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Everything counted on this thread so far.
+pub fn counts() -> Counts {
+    COUNTS.with(Cell::get)
+}
+
+/// Runs `f` and returns what it allocated and freed on this thread.
+/// Measurements can be nested.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Counts) {
+    let before = counts();
+    let result = f();
+    (result, counts() - before)
+}
+
+/// One measured step of a [`Timeline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub label: String,
+    pub counts: Counts,
+}
+
+/// Steps measured one after the other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeline {
+    steps: Vec<Step>,
+}
+
+impl Timeline {
+    /// A timeline without any steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` as the next step and returns its result. Only what `f` does
+    /// is counted, not the bookkeeping of the timeline.
+    pub fn step<R>(&mut self, label: &str, f: impl FnOnce() -> R) -> R {
+        let label = label.to_string();
+        self.steps.reserve(1);
+        let (result, counts) = measure(f);
+        self.steps.push(Step { label, counts });
+        result
+    }
+
+    /// Every step measured so far, in the order they ran.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The step with the given label.
+    pub fn find(&self, label: &str) -> Option<&Step> {
+        self.steps.iter().find(|step| step.label == label)
+    }
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .steps
+            .iter()
+            .map(|step| step.label.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("step".len());
+        write!(
+            f,
+            "{:<width$}  {:>6}  {:>8}  {:>5}  {:>9}  {:>7}",
+            "step", "allocs", "reallocs", "frees", "allocated", "freed"
+        )?;
+        for Step { label, counts } in &self.steps {
+            write!(
+                f,
+                "\n{:<width$}  {:>6}  {:>8}  {:>5}  {:>9}  {:>7}",
+                label,
+                counts.allocations,
+                counts.reallocations,
+                counts.deallocations,
+                counts.bytes_allocated,
+                counts.bytes_freed
+            )?;
+        }
+        Ok(())
+    }
+}
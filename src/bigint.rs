@@ -0,0 +1,265 @@
+//! Whole numbers of any size, added and multiplied by hand the way it is done
+//! on paper. Just enough for the [`sequences`](crate::sequences).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Rem};
+use std::str::FromStr;
+
+/// A whole number of any size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    // Least significant first, with no zero limbs at the end, so that every
+    // number has one representation and zero has no limbs at all.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// The number 0, the same as `BigUint::default()`.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Whether the number is 0.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Whether the number is divisible by 2; 0 is even.
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().map_or(0, |low| low % 2) == 0
+    }
+
+    /// How many bits the number needs; 0 for zero.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => (self.limbs.len() as u64 - 1) * 32 + u64::from(32 - top.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    /// The number divided by two, rounded down.
+    pub fn half(&self) -> Self {
+        let mut limbs = self.limbs.clone();
+        let mut carry = 0;
+        for limb in limbs.iter_mut().rev() {
+            let low_bit = *limb & 1;
+            *limb = (*limb >> 1) | (carry << 31);
+            carry = low_bit;
+        }
+        Self::from_limbs(limbs)
+    }
+
+    /// `None` if `divisor` is zero.
+    pub fn checked_rem(&self, divisor: &BigUint) -> Option<BigUint> {
+        if divisor.is_zero() {
+            return None;
+        }
+        // Divisors that fit in a limb, like those of a primality test, are
+        // divided a limb at a time.
+        if let [limb] = divisor.limbs[..] {
+            return Some(BigUint::from(self.div_rem_small(limb).1));
+        }
+        // Long division in base 2: bring the bits down one at a time, most
+        // significant first, and subtract the divisor whenever it fits.
+        let mut remainder = BigUint::zero();
+        for bit in (0..self.bits()).rev() {
+            remainder = remainder.shl1(self.bit(bit));
+            if remainder >= *divisor {
+                remainder = remainder.sub(divisor);
+            }
+        }
+        Some(remainder)
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    fn bit(&self, index: u64) -> bool {
+        let limb = self.limbs[(index / 32) as usize];
+        (limb >> (index % 32)) & 1 == 1
+    }
+
+    // Twice the number, plus one if `low_bit` is set.
+    fn shl1(&self, low_bit: bool) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = u32::from(low_bit);
+        for limb in &self.limbs {
+            limbs.push((limb << 1) | carry);
+            carry = limb >> 31;
+        }
+        limbs.push(carry);
+        Self::from_limbs(limbs)
+    }
+
+    // Only called with `other` no larger than `self`.
+    fn sub(&self, other: &BigUint) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = false;
+        for (index, limb) in self.limbs.iter().enumerate() {
+            let other = other.limbs.get(index).copied().unwrap_or(0);
+            let (difference, under) = limb.overflowing_sub(other);
+            let (difference, under_again) = difference.overflowing_sub(u32::from(borrow));
+            limbs.push(difference);
+            borrow = under || under_again;
+        }
+        debug_assert!(!borrow, "subtracted a larger number");
+        Self::from_limbs(limbs)
+    }
+
+    // The number times `factor`, plus `carry`.
+    fn mul_small(&self, factor: u32, mut carry: u32) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        for limb in &self.limbs {
+            let product = u64::from(*limb) * u64::from(factor) + u64::from(carry);
+            limbs.push(product as u32);
+            carry = (product >> 32) as u32;
+        }
+        limbs.push(carry);
+        Self::from_limbs(limbs)
+    }
+
+    // The quotient and remainder of dividing by `divisor`, which is not zero.
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut remainder: u64 = 0;
+        for (index, limb) in self.limbs.iter().enumerate().rev() {
+            let current = (remainder << 32) | u64::from(*limb);
+            limbs[index] = (current / u64::from(divisor)) as u32;
+            remainder = current % u64::from(divisor);
+        }
+        (Self::from_limbs(limbs), remainder as u32)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(value: u32) -> Self {
+        Self::from_limbs(vec![value])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Without zero limbs at the end, more limbs means a larger number.
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for index in 0..len {
+            let a = u64::from(self.limbs.get(index).copied().unwrap_or(0));
+            let b = u64::from(other.limbs.get(index).copied().unwrap_or(0));
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                // At most (2^32 - 1)^2 + 2 * (2^32 - 1), which fits in a u64.
+                let product = u64::from(*a) * u64::from(*b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+/// Panics if `divisor` is zero, like `%` on the built-in types.
+impl Rem for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, divisor: &BigUint) -> BigUint {
+        self.checked_rem(divisor)
+            .expect("attempt to calculate the remainder with a divisor of zero")
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Nine decimal digits at a time, least significant first.
+        const CHUNK: u32 = 1_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_small(CHUNK);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+        let mut digits = match chunks.pop() {
+            Some(top) => top.to_string(),
+            None => String::from("0"),
+        };
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError(String);
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a whole number", self.0)
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+// Decimal digits, with `_` allowed between them like in Rust literals.
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let invalid = || ParseBigUintError(text.to_string());
+        if !text.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let mut number = BigUint::zero();
+        for c in text.chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(10).ok_or_else(invalid)?;
+            number = number.mul_small(10, digit);
+        }
+        Ok(number)
+    }
+}
@@ -0,0 +1,107 @@
+//////////////////////////
+// Chapters
+//////////////////////////
+
+//! The [`Chapter`] trait that every partNN.rs file implements. build.rs lists
+//! those files as the built-in chapters, so a new chapter only needs its own
+//! file; chapters from other crates can be added to a [`Registry`].
+
+use std::io::{self, Write};
+
+use crate::alloc_trace::Timeline;
+
+/// One chapter of the book: what it is called and what it prints.
+pub trait Chapter {
+    /// Chapter number as written in the file name, e.g. "07".
+    fn id(&self) -> &str;
+
+    /// Title, written the same as in the banner comment at the top of the
+    /// chapter file.
+    fn title(&self) -> &str;
+
+    /// Short keywords describing what the chapter covers.
+    fn topics(&self) -> &[&str];
+
+    /// Everything the chapter prints goes to `out`.
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    /// Chapters that wait for input on stdin, such as the guessing game, say so
+    /// here so that automated runs like the golden-output tests can skip them.
+    fn interactive(&self) -> bool {
+        false
+    }
+
+    /// Runs the chapter as steps of `timeline` to see what each one allocates
+    /// (see `crate::alloc_trace`). By default the whole chapter is one step,
+    /// with its output thrown away; chapters about memory split it up.
+    /// Interactive chapters are not traced, since they would wait for input.
+    fn trace(&self, timeline: &mut Timeline) -> io::Result<()> {
+        timeline.step(self.title(), || self.run(&mut io::sink()))
+    }
+}
+
+/// The chapters the command line knows about, ordered by chapter number.
+pub struct Registry {
+    chapters: Vec<Box<dyn Chapter>>,
+}
+
+impl Registry {
+    /// A registry without any chapters.
+    pub fn new() -> Self {
+        Self {
+            chapters: Vec::new(),
+        }
+    }
+
+    /// A registry holding every partNN chapter of this crate.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for chapter in crate::builtin_chapters() {
+            registry.register(chapter);
+        }
+        registry
+    }
+
+    /// Adds a chapter, keeping the registry ordered by chapter number. A chapter
+    /// with the same id as an existing one replaces it.
+    pub fn register(&mut self, chapter: Box<dyn Chapter>) -> &mut Self {
+        let key = sort_key(chapter.id());
+        match self
+            .chapters
+            .binary_search_by(|existing| sort_key(existing.id()).cmp(&key))
+        {
+            Ok(index) => self.chapters[index] = chapter,
+            Err(index) => self.chapters.insert(index, chapter),
+        }
+        self
+    }
+
+    /// Chapters can be asked for as "7", "07" or "part07".
+    pub fn find(&self, id: &str) -> Option<&dyn Chapter> {
+        let key = sort_key(id.strip_prefix("part").unwrap_or(id));
+        self.chapters
+            .iter()
+            .find(|chapter| sort_key(chapter.id()) == key)
+            .map(|chapter| chapter.as_ref())
+    }
+
+    /// Every chapter, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Chapter> {
+        self.chapters.iter().map(|chapter| chapter.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Numeric ids sort by value ("7" and "07" are the same chapter) and come before
+// any non-numeric ids, which sort alphabetically.
+fn sort_key(id: &str) -> (bool, u32, String) {
+    match id.parse::<u32>() {
+        Ok(number) => (false, number, String::new()),
+        Err(_) => (true, 0, id.to_string()),
+    }
+}
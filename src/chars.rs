@@ -0,0 +1,450 @@
+//! What is inside a `char`: its code point, its UTF-8 and UTF-16 encodings,
+//! and its general category, from a table of the common scripts and symbols
+//! or, for other letters, the standard library's case checks.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The scalar values below the surrogates.
+pub const LOW_SCALARS: RangeInclusive<u32> = 0x0000..=0xD7FF;
+
+/// The code points UTF-16 uses in pairs, which are not scalar values.
+pub const SURROGATES: RangeInclusive<u32> = 0xD800..=0xDFFF;
+
+/// The scalar values above the surrogates.
+pub const HIGH_SCALARS: RangeInclusive<u32> = 0xE000..=0x10FFFF;
+
+/// The Unicode general categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    UppercaseLetter,
+    LowercaseLetter,
+    TitlecaseLetter,
+    ModifierLetter,
+    OtherLetter,
+    NonspacingMark,
+    SpacingMark,
+    EnclosingMark,
+    DecimalNumber,
+    LetterNumber,
+    OtherNumber,
+    ConnectorPunctuation,
+    DashPunctuation,
+    OpenPunctuation,
+    ClosePunctuation,
+    InitialPunctuation,
+    FinalPunctuation,
+    OtherPunctuation,
+    MathSymbol,
+    CurrencySymbol,
+    ModifierSymbol,
+    OtherSymbol,
+    SpaceSeparator,
+    LineSeparator,
+    ParagraphSeparator,
+    Control,
+    Format,
+    Surrogate,
+    PrivateUse,
+    Unassigned,
+}
+
+impl Category {
+    /// The two-letter abbreviation, like `Lu`.
+    pub fn abbreviation(self) -> &'static str {
+        use Category::*;
+        match self {
+            UppercaseLetter => "Lu",
+            LowercaseLetter => "Ll",
+            TitlecaseLetter => "Lt",
+            ModifierLetter => "Lm",
+            OtherLetter => "Lo",
+            NonspacingMark => "Mn",
+            SpacingMark => "Mc",
+            EnclosingMark => "Me",
+            DecimalNumber => "Nd",
+            LetterNumber => "Nl",
+            OtherNumber => "No",
+            ConnectorPunctuation => "Pc",
+            DashPunctuation => "Pd",
+            OpenPunctuation => "Ps",
+            ClosePunctuation => "Pe",
+            InitialPunctuation => "Pi",
+            FinalPunctuation => "Pf",
+            OtherPunctuation => "Po",
+            MathSymbol => "Sm",
+            CurrencySymbol => "Sc",
+            ModifierSymbol => "Sk",
+            OtherSymbol => "So",
+            SpaceSeparator => "Zs",
+            LineSeparator => "Zl",
+            ParagraphSeparator => "Zp",
+            Control => "Cc",
+            Format => "Cf",
+            Surrogate => "Cs",
+            PrivateUse => "Co",
+            Unassigned => "Cn",
+        }
+    }
+
+    /// The category's name in words, like "uppercase letter".
+    pub fn name(self) -> &'static str {
+        use Category::*;
+        match self {
+            UppercaseLetter => "uppercase letter",
+            LowercaseLetter => "lowercase letter",
+            TitlecaseLetter => "titlecase letter",
+            ModifierLetter => "modifier letter",
+            OtherLetter => "other letter",
+            NonspacingMark => "nonspacing mark",
+            SpacingMark => "spacing mark",
+            EnclosingMark => "enclosing mark",
+            DecimalNumber => "decimal number",
+            LetterNumber => "letter number",
+            OtherNumber => "other number",
+            ConnectorPunctuation => "connector punctuation",
+            DashPunctuation => "dash punctuation",
+            OpenPunctuation => "open punctuation",
+            ClosePunctuation => "close punctuation",
+            InitialPunctuation => "initial punctuation",
+            FinalPunctuation => "final punctuation",
+            OtherPunctuation => "other punctuation",
+            MathSymbol => "math symbol",
+            CurrencySymbol => "currency symbol",
+            ModifierSymbol => "modifier symbol",
+            OtherSymbol => "other symbol",
+            SpaceSeparator => "space separator",
+            LineSeparator => "line separator",
+            ParagraphSeparator => "paragraph separator",
+            Control => "control",
+            Format => "format",
+            Surrogate => "surrogate",
+            PrivateUse => "private use",
+            Unassigned => "unassigned",
+        }
+    }
+
+    /// The category of `c`, from this module's table or else, for letters
+    /// and spaces, from what the standard library knows about `c`.
+    pub fn of(c: char) -> Option<Category> {
+        let code_point = u32::from(c);
+        let index = CATEGORIES.partition_point(|(_, last, _)| *last < code_point);
+        match CATEGORIES.get(index) {
+            Some((first, _, category)) if *first <= code_point => Some(*category),
+            _ => Self::from_properties(c),
+        }
+    }
+
+    // Exact for the Latin Extended blocks, which the table leaves out. Other
+    // scripts have letters that only look cased, like the modifier letter ʰ,
+    // which would pass for a lowercase letter here.
+    fn from_properties(c: char) -> Option<Category> {
+        use Category::*;
+        if c.is_uppercase() {
+            Some(UppercaseLetter)
+        } else if c.is_lowercase() {
+            Some(LowercaseLetter)
+        } else if c.is_alphabetic() {
+            // Titlecase letters like ǅ change both ways.
+            let titlecase = !c.to_uppercase().eq([c]) && !c.to_lowercase().eq([c]);
+            Some(if titlecase {
+                TitlecaseLetter
+            } else {
+                OtherLetter
+            })
+        } else if c.is_whitespace() {
+            Some(SpaceSeparator)
+        } else if c.is_control() {
+            Some(Control)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.abbreviation(), self.name())
+    }
+}
+
+// (first, last, category), sorted and not overlapping. Checked against the
+// Unicode Character Database: all of ASCII and Latin-1, then the letters and
+// digits of common scripts, punctuation, symbols, CJK and private use.
+#[rustfmt::skip]
+const CATEGORIES: &[(u32, u32, Category)] = {
+    use Category::*;
+    &[
+        (0x0000, 0x001F, Control),
+        (0x0020, 0x0020, SpaceSeparator),
+        (0x0021, 0x0023, OtherPunctuation),
+        (0x0024, 0x0024, CurrencySymbol),
+        (0x0025, 0x0027, OtherPunctuation),
+        (0x0028, 0x0028, OpenPunctuation),
+        (0x0029, 0x0029, ClosePunctuation),
+        (0x002A, 0x002A, OtherPunctuation),
+        (0x002B, 0x002B, MathSymbol),
+        (0x002C, 0x002C, OtherPunctuation),
+        (0x002D, 0x002D, DashPunctuation),
+        (0x002E, 0x002F, OtherPunctuation),
+        (0x0030, 0x0039, DecimalNumber),
+        (0x003A, 0x003B, OtherPunctuation),
+        (0x003C, 0x003E, MathSymbol),
+        (0x003F, 0x0040, OtherPunctuation),
+        (0x0041, 0x005A, UppercaseLetter),
+        (0x005B, 0x005B, OpenPunctuation),
+        (0x005C, 0x005C, OtherPunctuation),
+        (0x005D, 0x005D, ClosePunctuation),
+        (0x005E, 0x005E, ModifierSymbol),
+        (0x005F, 0x005F, ConnectorPunctuation),
+        (0x0060, 0x0060, ModifierSymbol),
+        (0x0061, 0x007A, LowercaseLetter),
+        (0x007B, 0x007B, OpenPunctuation),
+        (0x007C, 0x007C, MathSymbol),
+        (0x007D, 0x007D, ClosePunctuation),
+        (0x007E, 0x007E, MathSymbol),
+        (0x007F, 0x009F, Control),
+        (0x00A0, 0x00A0, SpaceSeparator),
+        (0x00A1, 0x00A1, OtherPunctuation),
+        (0x00A2, 0x00A5, CurrencySymbol),
+        (0x00A6, 0x00A6, OtherSymbol),
+        (0x00A7, 0x00A7, OtherPunctuation),
+        (0x00A8, 0x00A8, ModifierSymbol),
+        (0x00A9, 0x00A9, OtherSymbol),
+        (0x00AA, 0x00AA, OtherLetter),
+        (0x00AB, 0x00AB, InitialPunctuation),
+        (0x00AC, 0x00AC, MathSymbol),
+        (0x00AD, 0x00AD, Format),
+        (0x00AE, 0x00AE, OtherSymbol),
+        (0x00AF, 0x00AF, ModifierSymbol),
+        (0x00B0, 0x00B0, OtherSymbol),
+        (0x00B1, 0x00B1, MathSymbol),
+        (0x00B2, 0x00B3, OtherNumber),
+        (0x00B4, 0x00B4, ModifierSymbol),
+        (0x00B5, 0x00B5, LowercaseLetter),
+        (0x00B6, 0x00B7, OtherPunctuation),
+        (0x00B8, 0x00B8, ModifierSymbol),
+        (0x00B9, 0x00B9, OtherNumber),
+        (0x00BA, 0x00BA, OtherLetter),
+        (0x00BB, 0x00BB, FinalPunctuation),
+        (0x00BC, 0x00BE, OtherNumber),
+        (0x00BF, 0x00BF, OtherPunctuation),
+        (0x00C0, 0x00D6, UppercaseLetter),
+        (0x00D7, 0x00D7, MathSymbol),
+        (0x00D8, 0x00DE, UppercaseLetter),
+        (0x00DF, 0x00F6, LowercaseLetter),
+        (0x00F7, 0x00F7, MathSymbol),
+        (0x00F8, 0x00FF, LowercaseLetter),
+        (0x0300, 0x036F, NonspacingMark),
+        (0x0391, 0x03A1, UppercaseLetter),
+        (0x03A3, 0x03A9, UppercaseLetter),
+        (0x03B1, 0x03C9, LowercaseLetter),
+        (0x0400, 0x042F, UppercaseLetter),
+        (0x0430, 0x045F, LowercaseLetter),
+        (0x05D0, 0x05EA, OtherLetter),
+        (0x0621, 0x063A, OtherLetter),
+        (0x0641, 0x064A, OtherLetter),
+        (0x0660, 0x0669, DecimalNumber),
+        (0x0966, 0x096F, DecimalNumber),
+        (0x2000, 0x200A, SpaceSeparator),
+        (0x200B, 0x200F, Format),
+        (0x2010, 0x2015, DashPunctuation),
+        (0x2016, 0x2017, OtherPunctuation),
+        (0x2018, 0x2018, InitialPunctuation),
+        (0x2019, 0x2019, FinalPunctuation),
+        (0x201A, 0x201A, OpenPunctuation),
+        (0x201B, 0x201C, InitialPunctuation),
+        (0x201D, 0x201D, FinalPunctuation),
+        (0x201E, 0x201E, OpenPunctuation),
+        (0x201F, 0x201F, InitialPunctuation),
+        (0x2020, 0x2027, OtherPunctuation),
+        (0x2028, 0x2028, LineSeparator),
+        (0x2029, 0x2029, ParagraphSeparator),
+        (0x202A, 0x202E, Format),
+        (0x202F, 0x202F, SpaceSeparator),
+        (0x20A0, 0x20C0, CurrencySymbol),
+        (0x2102, 0x2102, UppercaseLetter),
+        (0x2115, 0x2115, UppercaseLetter),
+        (0x2119, 0x211D, UppercaseLetter),
+        (0x2122, 0x2122, OtherSymbol),
+        (0x2124, 0x2124, UppercaseLetter),
+        (0x2190, 0x2194, MathSymbol),
+        (0x2195, 0x2199, OtherSymbol),
+        (0x2200, 0x22FF, MathSymbol),
+        (0x2500, 0x257F, OtherSymbol),
+        (0x2600, 0x266E, OtherSymbol),
+        (0x266F, 0x266F, MathSymbol),
+        (0x2670, 0x26FF, OtherSymbol),
+        (0x3000, 0x3000, SpaceSeparator),
+        (0x3001, 0x3003, OtherPunctuation),
+        (0x3041, 0x3096, OtherLetter),
+        (0x30A1, 0x30FA, OtherLetter),
+        (0x4E00, 0x9FFF, OtherLetter),
+        (0xAC00, 0xD7A3, OtherLetter),
+        (0xE000, 0xF8FF, PrivateUse),
+        (0xFE00, 0xFE0F, NonspacingMark),
+        (0xFEFF, 0xFEFF, Format),
+        (0x1F300, 0x1F3FA, OtherSymbol),
+        (0x1F3FB, 0x1F3FF, ModifierSymbol),
+        (0x1F400, 0x1F64F, OtherSymbol),
+        (0x1F680, 0x1F6C5, OtherSymbol),
+        (0x1F900, 0x1F9FF, OtherSymbol),
+        (0x20000, 0x2A6DF, OtherLetter),
+        (0xF0000, 0xFFFFD, PrivateUse),
+        (0x100000, 0x10FFFD, PrivateUse),
+    ]
+};
+
+/// One scalar value, taken apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharInfo {
+    pub c: char,
+}
+
+impl CharInfo {
+    pub fn new(c: char) -> Self {
+        Self { c }
+    }
+
+    pub fn code_point(&self) -> u32 {
+        u32::from(self.c)
+    }
+
+    /// One to four bytes.
+    pub fn utf8(&self) -> Vec<u8> {
+        let mut buffer = [0; 4];
+        self.c.encode_utf8(&mut buffer).as_bytes().to_vec()
+    }
+
+    /// One unit, or a surrogate pair above U+FFFF.
+    pub fn utf16(&self) -> Vec<u16> {
+        let mut buffer = [0; 2];
+        self.c.encode_utf16(&mut buffer).to_vec()
+    }
+
+    pub fn category(&self) -> Option<Category> {
+        Category::of(self.c)
+    }
+
+    /// Which of the two ranges of scalar values the char is in.
+    pub fn range(&self) -> RangeInclusive<u32> {
+        if LOW_SCALARS.contains(&self.code_point()) {
+            LOW_SCALARS
+        } else {
+            HIGH_SCALARS
+        }
+    }
+}
+
+impl fmt::Display for CharInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let utf8: Vec<String> = self.utf8().iter().map(|b| format!("{:02x}", b)).collect();
+        let utf16: Vec<String> = self.utf16().iter().map(|u| format!("{:04x}", u)).collect();
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        writeln!(f, "'{}' U+{:04X}", self.c.escape_debug(), self.code_point())?;
+        writeln!(
+            f,
+            "  UTF-8     {} ({} byte{})",
+            utf8.join(" "),
+            utf8.len(),
+            plural(utf8.len())
+        )?;
+        writeln!(
+            f,
+            "  UTF-16    {} ({} unit{})",
+            utf16.join(" "),
+            utf16.len(),
+            plural(utf16.len())
+        )?;
+        match self.category() {
+            Some(category) => writeln!(f, "  category  {}", category)?,
+            None => writeln!(f, "  category  not in the table")?,
+        }
+        let range = self.range();
+        write!(
+            f,
+            "  range     U+{:04X}..=U+{:04X}",
+            range.start(),
+            range.end()
+        )
+    }
+}
+
+/// Checks that `code_point` is a scalar value.
+pub fn to_char(code_point: u32) -> Result<char, CharError> {
+    if SURROGATES.contains(&code_point) {
+        return Err(CharError::Surrogate(code_point));
+    }
+    char::from_u32(code_point).ok_or(CharError::TooLarge(code_point))
+}
+
+/// Reads a code point written as `U+2124`, `\u{2124}` or `0x2124`.
+pub fn parse_code_point(input: &str) -> Result<char, CharError> {
+    let text = input.trim();
+    let digits = text
+        .strip_prefix("U+")
+        .or_else(|| text.strip_prefix("u+"))
+        .or_else(|| text.strip_prefix("0x"))
+        .or_else(|| {
+            text.strip_prefix("\\u{")
+                .and_then(|rest| rest.strip_suffix('}'))
+        })
+        .ok_or_else(|| CharError::NotACodePoint(text.to_string()))?;
+    let code_point =
+        u32::from_str_radix(digits, 16).map_err(|_| CharError::NotACodePoint(text.to_string()))?;
+    to_char(code_point)
+}
+
+// Whether `token` is written like a code point rather than as text.
+fn is_code_point(token: &str) -> bool {
+    ["U+", "u+", "0x", "\\u{"]
+        .iter()
+        .any(|prefix| token.starts_with(prefix) && token.len() > prefix.len())
+}
+
+/// Takes apart every char of `input`. If `input` is code points, like
+/// `U+48 U+49`, those are taken apart instead of the text.
+pub fn inspect(input: &str) -> Result<Vec<CharInfo>, CharError> {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    if !tokens.is_empty() && tokens.iter().all(|token| is_code_point(token)) {
+        return tokens
+            .into_iter()
+            .map(|token| parse_code_point(token).map(CharInfo::new))
+            .collect();
+    }
+    Ok(input.chars().map(CharInfo::new).collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharError {
+    NotACodePoint(String),
+    /// In U+D800..=U+DFFF.
+    Surrogate(u32),
+    /// Above U+10FFFF.
+    TooLarge(u32),
+}
+
+impl fmt::Display for CharError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CharError::NotACodePoint(text) => write!(
+                f,
+                "'{}' is not a code point, expected hex digits like U+2124, \\u{{2124}} or 0x2124",
+                text
+            ),
+            CharError::Surrogate(code_point) => write!(
+                f,
+                "U+{:04X} is a surrogate, not a char: U+D800..=U+DFFF are only used in pairs by \
+                 UTF-16, for scalars above U+FFFF",
+                code_point
+            ),
+            CharError::TooLarge(code_point) => write!(
+                f,
+                "U+{:04X} is not a char: Unicode ends at U+10FFFF",
+                code_point
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CharError {}
@@ -0,0 +1,1283 @@
+//////////////////////////
+// Command Line Interface
+//////////////////////////
+
+//! The `hello-rust` command line: [`parse`] turns the arguments into a
+//! [`Command`] and [`execute`] runs it. [`USAGE`] lists every command and its
+//! flags; `--lang <code>` before the command picks the language of the output.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::alloc_trace::{self, Timeline};
+use crate::bigint::BigUint;
+use crate::chapter::{Chapter, Registry};
+use crate::chars::{self, CharInfo};
+use crate::duration::Span;
+use crate::guessing::net::{self, Client, Server, ServerConfig, DEFAULT_ADDR};
+use crate::guessing::{
+    self, Code, CodeGame, CodeRules, Difficulty, GameSettings, HangmanGame, HintSettings,
+    Letters, MinimaxSolver, ReplayError, ScoreTable, ScoringModel, Session, WordList,
+    DEFAULT_MAX_MISSES, DEFAULT_REPLAY_DIR, DEFAULT_SCORE_FILE, MAX_SCORES_PER_RANGE,
+    SCORING_MODELS, STRATEGIES,
+};
+use crate::i18n::{self, Messages, DEFAULT_LOCALE_DIR};
+use crate::layout;
+use crate::literals::{self, Base, IntType, LiteralError, Value};
+use crate::numbers;
+use crate::overflow::{self, ExploreError, Op, Report};
+use crate::part02;
+use crate::rules::RuleSet;
+use crate::sequences::{Natural, Sequence, SequenceError};
+use crate::units::{Quantity, Unit, UnitsError};
+
+/// Printed by `help`, and after any error in the arguments.
+pub const USAGE: &str = "\
+Usage:
+    hello-rust [--lang <code>] <command>, where <command> is one of
+    hello-rust list
+    hello-rust run <chapter>
+    hello-rust run --all
+    hello-rust play [--difficulty easy|normal|hard] [--range <from>..=<to>]
+                    [--max-attempts <n>] [--reveal-secret]
+                    [--scores-file <path> | --no-scores]
+                    [--record-dir <dir> | --no-record]
+                    [--hints <n>] [--hot-cold]
+    hello-rust scores [--range <from>..=<to>] [--top <n>] [--reset]
+                      [--scores-file <path>] [--scoring guesses|range]
+                      [--since <duration, like 7d or 1h30m>]
+    hello-rust replay <file>
+    hello-rust bulls-and-cows [--length <n>] [--digits <n>] [--repeats]
+                              [--max-attempts <n>] [--solve] [--secret <code>]
+    hello-rust hangman [--words <file> | --word <word>] [--letters char|grapheme]
+                       [--max-misses <n>]
+    hello-rust reverse [--difficulty easy|normal|hard] [--range <from>..=<to>]
+    hello-rust simulate [--strategy binary|random|human|all] [--games <n>]
+                        [--seed <n>] [--difficulty easy|normal|hard]
+                        [--range <from>..=<to>] [--max-attempts <n>]
+    hello-rust serve [--addr <host:port>] [--rounds <n>] [--seed <n>]
+                     [--difficulty easy|normal|hard] [--range <from>..=<to>]
+    hello-rust connect [--addr <host:port>] [--name <name>]
+    hello-rust convert <literal> [--type <int type>]
+    hello-rust overflow add|sub|mul|div|rem|neg|pow|shl <lhs> [<rhs>]
+                        [--type <int type>] [--json]
+    hello-rust rules [--file <rules file>] [--range <from>..=<to>]
+    hello-rust sequence fibonacci|factorial|primes|collatz <n> | <from>..=<to>
+                        [--start <n>] [--big]
+    hello-rust inspect-char <text> | <code point like U+2124>...
+    hello-rust layout
+    hello-rust units <number><unit> [--to <unit>]
+    hello-rust trace <chapter>...          (built with --features alloc-trace)
+    hello-rust help";
+
+/// What to do, as parsed from the command line.
+pub enum Command {
+    /// `list`: every chapter with its title and topics.
+    List,
+    /// `run <chapter>`: a single chapter.
+    Run(String),
+    /// `run --all`: every chapter in order.
+    RunAll,
+    /// `play`: the guessing game. A `difficulty` of `None` asks for one
+    /// before the game starts.
+    Play {
+        difficulty: Option<Difficulty>,
+        reveal_secret: bool,
+        score_file: Option<PathBuf>,
+        replay_dir: Option<PathBuf>,
+        hints: HintSettings,
+    },
+    /// `scores`: the guessing game's high-score table.
+    Scores {
+        score_file: PathBuf,
+        range: Option<RangeInclusive<u32>>,
+        top: usize,
+        reset: bool,
+        scoring: String,
+        /// Only scores won this recently.
+        since: Option<Span>,
+    },
+    /// `replay <file>`: plays a recorded game again and checks that it still
+    /// goes the same way.
+    Replay(PathBuf),
+    /// `bulls-and-cows`: guess a code of digits instead of a number. With
+    /// `solve`, the solver plays against `secret` (or a random code).
+    BullsAndCows {
+        rules: CodeRules,
+        max_attempts: Option<u32>,
+        solve: bool,
+        secret: Option<Code>,
+    },
+    /// `hangman`: guess the letters of a word. Without `word`, one is picked
+    /// from `words` (or the built-in list).
+    Hangman {
+        words: Option<PathBuf>,
+        word: Option<String>,
+        letters: Letters,
+        max_misses: u32,
+    },
+    /// `reverse`: the program guesses a number the player thinks of.
+    Reverse {
+        range: RangeInclusive<u32>,
+    },
+    /// `simulate`: bots play the guessing game many times. Every preset is
+    /// simulated when `difficulties` is not narrowed down.
+    Simulate {
+        strategies: Vec<String>,
+        difficulties: Vec<Difficulty>,
+        games: u32,
+        seed: u64,
+    },
+    /// `serve`: hosts the guessing game as a race over TCP.
+    Serve {
+        addr: String,
+        config: ServerConfig,
+    },
+    /// `connect`: joins a race hosted with `serve`. A `name` of `None` asks
+    /// for one once connected.
+    Connect {
+        addr: String,
+        name: Option<String>,
+    },
+    /// `convert <literal>`: an integer literal in every base. An unsuffixed
+    /// literal is taken as `unsuffixed`.
+    Convert {
+        literal: String,
+        unsuffixed: IntType,
+    },
+    /// `overflow <op> <lhs> [<rhs>]`: what each kind of arithmetic does on
+    /// overflow. Checked by parse, so only printed by execute.
+    Overflow {
+        report: Report,
+        json: bool,
+    },
+    /// `rules`: numbers labelled by rules, FizzBuzz unless given a file.
+    Rules {
+        rules: RuleSet,
+        range: RangeInclusive<u32>,
+    },
+    /// `sequence <name> <n>`: a term, or a range of terms, of a sequence.
+    /// They are worked out and printed one at a time by execute, since a long
+    /// range of big numbers takes a while.
+    Sequence {
+        sequence: Sequence,
+        start: String,
+        indexes: RangeInclusive<u32>,
+        big: bool,
+    },
+    /// `inspect-char <text>`: the code point, encodings and category of every
+    /// char.
+    InspectChar(Vec<CharInfo>),
+    /// `layout`: the size and alignment of the chapters' types.
+    Layout,
+    /// `units <quantity>`: the quantity as given, then the same in the other
+    /// units of its kind.
+    Units(Vec<Quantity>),
+    /// `trace <chapter>...`: what each chapter allocates, step by step. Needs
+    /// `--features alloc-trace`.
+    Trace(Vec<String>),
+    /// `help`: prints [`USAGE`].
+    Help,
+}
+
+/// Why the command line could not be parsed or the command failed.
+#[derive(Debug)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingChapter,
+    UnknownChapter(String),
+    MissingReplay,
+    MissingLiteral,
+    InvalidLiteral(String, LiteralError),
+    UnexpectedArgument(String),
+    MissingValue(&'static str),
+    InvalidValue {
+        flag: &'static str,
+        value: String,
+        reason: String,
+    },
+    /// Options that are fine one by one but not together.
+    InvalidOptions(String),
+    Io(io::Error),
+    Replay(ReplayError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "no command given"),
+            CliError::UnknownCommand(cmd) => write!(f, "unknown command '{}'", cmd),
+            CliError::MissingChapter => write!(f, "'run' needs a chapter number or --all"),
+            CliError::UnknownChapter(id) => write!(
+                f,
+                "unknown chapter '{}' (use 'hello-rust list' to see the available chapters)",
+                id
+            ),
+            CliError::MissingReplay => write!(f, "'replay' needs a replay file"),
+            CliError::MissingLiteral => write!(f, "'convert' needs an integer literal"),
+            CliError::InvalidLiteral(literal, error) => {
+                write!(f, "invalid literal '{}': {}", literal, error)
+            }
+            CliError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            CliError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            CliError::InvalidValue {
+                flag,
+                value,
+                reason,
+            } => write!(f, "invalid value '{}' for {}: {}", value, flag, reason),
+            CliError::InvalidOptions(reason) => write!(f, "invalid options: {}", reason),
+            CliError::Io(error) => write!(f, "{}", error),
+            CliError::Replay(error) => write!(f, "replay failed: {}", error),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Io(error)
+    }
+}
+
+impl CliError {
+    /// Wrong arguments exit with 2, failures while running with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Io(_) | CliError::Replay(_) => 1,
+            _ => 2,
+        }
+    }
+}
+
+// The arguments following the command name.
+struct Args<'a> {
+    rest: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    fn new(rest: &'a [String]) -> Self {
+        Self { rest: rest.iter() }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.rest.next().map(String::as_str)
+    }
+
+    // The value following `flag`, as in `--range 1..=500`.
+    fn value(&mut self, flag: &'static str) -> Result<&'a str, CliError> {
+        self.next().ok_or(CliError::MissingValue(flag))
+    }
+
+    fn parsed<T>(&mut self, flag: &'static str) -> Result<T, CliError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.value(flag)?;
+        value.parse().map_err(|error: T::Err| CliError::InvalidValue {
+            flag,
+            value: value.to_string(),
+            reason: error.to_string(),
+        })
+    }
+
+    // A limit such as `--max-attempts 3`; a limit of 0 would end the game
+    // before it starts.
+    fn limit(&mut self, flag: &'static str) -> Result<u32, CliError> {
+        match self.parsed(flag)? {
+            0 => Err(CliError::InvalidValue {
+                flag,
+                value: "0".to_string(),
+                reason: "must be at least 1".to_string(),
+            }),
+            limit => Ok(limit),
+        }
+    }
+
+    fn range(&mut self, flag: &'static str) -> Result<RangeInclusive<u32>, CliError> {
+        let value = self.value(flag)?;
+        numbers::parse_range(value).map_err(|error| CliError::InvalidValue {
+            flag,
+            value: value.to_string(),
+            reason: error.to_string(),
+        })
+    }
+
+    fn finish(&mut self) -> Result<(), CliError> {
+        match self.next() {
+            Some(extra) => Err(CliError::UnexpectedArgument(extra.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `std::env::args()` yields the program name first; callers pass what follows it.
+pub fn parse(args: &[String]) -> Result<Command, CliError> {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => return Err(CliError::MissingCommand),
+    };
+    let mut args = Args::new(rest);
+
+    let command = match command {
+        "list" => Command::List,
+        "help" | "-h" | "--help" => Command::Help,
+        "run" => match args.next() {
+            Some("--all") => Command::RunAll,
+            Some(target) => Command::Run(target.to_string()),
+            None => return Err(CliError::MissingChapter),
+        },
+        "play" => parse_play(&mut args)?,
+        "scores" => parse_scores(&mut args)?,
+        "replay" => match args.next() {
+            Some(file) => Command::Replay(PathBuf::from(file)),
+            None => return Err(CliError::MissingReplay),
+        },
+        "bulls-and-cows" => parse_bulls_and_cows(&mut args)?,
+        "hangman" => parse_hangman(&mut args)?,
+        "reverse" => parse_reverse(&mut args)?,
+        "simulate" => parse_simulate(&mut args)?,
+        "serve" => parse_serve(&mut args)?,
+        "connect" => parse_connect(&mut args)?,
+        "convert" => parse_convert(&mut args)?,
+        "overflow" => parse_overflow(&mut args)?,
+        "rules" => parse_rules(&mut args)?,
+        "sequence" => parse_sequence(&mut args)?,
+        "inspect-char" => parse_inspect_char(&mut args)?,
+        "layout" => {
+            args.finish()?;
+            Command::Layout
+        }
+        "units" => parse_units(&mut args)?,
+        "trace" => parse_trace(&mut args)?,
+        other => return Err(CliError::UnknownCommand(other.to_string())),
+    };
+
+    args.finish()?;
+    Ok(command)
+}
+
+fn parse_play(args: &mut Args) -> Result<Command, CliError> {
+    let mut preset: Option<Difficulty> = None;
+    let mut range = None;
+    let mut max_attempts = None;
+    let mut reveal_secret = false;
+    let mut score_file = Some(PathBuf::from(DEFAULT_SCORE_FILE));
+    let mut replay_dir = Some(PathBuf::from(DEFAULT_REPLAY_DIR));
+    let mut hints = HintSettings::default();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--difficulty" => preset = Some(args.parsed("--difficulty")?),
+            "--range" => range = Some(args.range("--range")?),
+            "--max-attempts" => max_attempts = Some(args.limit("--max-attempts")?),
+            "--reveal-secret" => reveal_secret = true,
+            "--scores-file" => score_file = Some(PathBuf::from(args.value("--scores-file")?)),
+            "--no-scores" => score_file = None,
+            "--record-dir" => replay_dir = Some(PathBuf::from(args.value("--record-dir")?)),
+            "--no-record" => replay_dir = None,
+            "--hints" => hints.budget = args.parsed("--hints")?,
+            "--hot-cold" => hints.hot_cold = true,
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    // A range or an attempt limit turns the preset (normal if none was given)
+    // into a custom difficulty.
+    let difficulty = if range.is_some() || max_attempts.is_some() {
+        let base = preset.unwrap_or_default();
+        Some(Difficulty::Custom {
+            range: range.unwrap_or_else(|| base.range()),
+            max_attempts: max_attempts.or_else(|| base.max_attempts()),
+        })
+    } else {
+        preset
+    };
+
+    Ok(Command::Play {
+        difficulty,
+        reveal_secret,
+        score_file,
+        replay_dir,
+        hints,
+    })
+}
+
+fn parse_scores(args: &mut Args) -> Result<Command, CliError> {
+    let mut score_file = PathBuf::from(DEFAULT_SCORE_FILE);
+    let mut range = None;
+    let mut top = MAX_SCORES_PER_RANGE;
+    let mut reset = false;
+    let mut scoring = SCORING_MODELS[0].to_string();
+    let mut since = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--scores-file" => score_file = PathBuf::from(args.value("--scores-file")?),
+            "--range" => range = Some(args.range("--range")?),
+            "--top" => top = args.parsed("--top")?,
+            "--reset" => reset = true,
+            "--since" => since = Some(args.parsed("--since")?),
+            "--scoring" => match args.value("--scoring")? {
+                name if SCORING_MODELS.contains(&name) => scoring = name.to_string(),
+                name => {
+                    return Err(CliError::InvalidValue {
+                        flag: "--scoring",
+                        value: name.to_string(),
+                        reason: format!("expected {}", SCORING_MODELS.join(" or ")),
+                    })
+                }
+            },
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    Ok(Command::Scores {
+        score_file,
+        range,
+        top,
+        reset,
+        scoring,
+        since,
+    })
+}
+
+fn parse_bulls_and_cows(args: &mut Args) -> Result<Command, CliError> {
+    let defaults = CodeRules::default();
+    let mut length = defaults.length();
+    let mut digits = defaults.digits();
+    let mut repeats = defaults.repeats();
+    let mut max_attempts = Some(10);
+    let mut solve = false;
+    let mut secret = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--length" => length = args.parsed("--length")?,
+            "--digits" => digits = args.parsed("--digits")?,
+            "--repeats" => repeats = true,
+            "--max-attempts" => max_attempts = Some(args.limit("--max-attempts")?),
+            "--solve" => solve = true,
+            "--secret" => secret = Some(args.value("--secret")?),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    let rules = CodeRules::new(length, digits, repeats)
+        .map_err(|error| CliError::InvalidOptions(error.to_string()))?;
+    let secret = match secret {
+        Some(secret) => Some(rules.parse(secret).map_err(|error| CliError::InvalidValue {
+            flag: "--secret",
+            value: secret.to_string(),
+            reason: error.to_string(),
+        })?),
+        None => None,
+    };
+    if secret.is_some() && !solve {
+        return Err(CliError::InvalidOptions(
+            "--secret is only used with --solve".to_string(),
+        ));
+    }
+    if solve {
+        // Checked here so that `execute` can build the solver without failing.
+        MinimaxSolver::new(rules).map_err(|error| CliError::InvalidOptions(error.to_string()))?;
+    }
+
+    Ok(Command::BullsAndCows {
+        rules,
+        max_attempts,
+        solve,
+        secret,
+    })
+}
+
+fn parse_hangman(args: &mut Args) -> Result<Command, CliError> {
+    let mut words = None;
+    let mut word = None;
+    let mut letters = Letters::default();
+    let mut max_misses = DEFAULT_MAX_MISSES;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--words" => words = Some(PathBuf::from(args.value("--words")?)),
+            "--word" => word = Some(args.value("--word")?.to_string()),
+            "--letters" => letters = args.parsed("--letters")?,
+            "--max-misses" => max_misses = args.limit("--max-misses")?,
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    if words.is_some() && word.is_some() {
+        return Err(CliError::InvalidOptions(
+            "--words and --word cannot be used together".to_string(),
+        ));
+    }
+    if let Some(word) = &word {
+        if let Err(error) = HangmanGame::new(word.trim(), letters) {
+            return Err(CliError::InvalidValue {
+                flag: "--word",
+                value: word.clone(),
+                reason: error.to_string(),
+            });
+        }
+    }
+
+    Ok(Command::Hangman {
+        words,
+        word,
+        letters,
+        max_misses,
+    })
+}
+
+fn parse_reverse(args: &mut Args) -> Result<Command, CliError> {
+    // The same default range as `play`.
+    let mut range = Difficulty::default().range();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            // The program needs no attempt limit, so only the range is used.
+            "--difficulty" => range = args.parsed::<Difficulty>("--difficulty")?.range(),
+            "--range" => range = args.range("--range")?,
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    Ok(Command::Reverse { range })
+}
+
+fn parse_simulate(args: &mut Args) -> Result<Command, CliError> {
+    let mut strategies: Vec<String> = STRATEGIES.iter().map(|name| name.to_string()).collect();
+    let mut preset: Option<Difficulty> = None;
+    let mut range = None;
+    let mut max_attempts = None;
+    let mut games = 10_000;
+    let mut seed = 0;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--strategy" => match args.value("--strategy")? {
+                "all" => strategies = STRATEGIES.iter().map(|name| name.to_string()).collect(),
+                name if STRATEGIES.contains(&name) => strategies = vec![name.to_string()],
+                name => {
+                    return Err(CliError::InvalidValue {
+                        flag: "--strategy",
+                        value: name.to_string(),
+                        reason: format!("expected {} or all", STRATEGIES.join(", ")),
+                    })
+                }
+            },
+            "--games" => games = args.parsed("--games")?,
+            "--seed" => seed = args.parsed("--seed")?,
+            "--difficulty" => preset = Some(args.parsed("--difficulty")?),
+            "--range" => range = Some(args.range("--range")?),
+            "--max-attempts" => max_attempts = Some(args.limit("--max-attempts")?),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    // Same rules as `play`, except that without any difficulty flag every
+    // preset is simulated.
+    let difficulties = if range.is_some() || max_attempts.is_some() {
+        let base = preset.unwrap_or_default();
+        vec![Difficulty::Custom {
+            range: range.unwrap_or_else(|| base.range()),
+            max_attempts: max_attempts.or_else(|| base.max_attempts()),
+        }]
+    } else {
+        match preset {
+            Some(preset) => vec![preset],
+            None => vec![Difficulty::Easy, Difficulty::Normal, Difficulty::Hard],
+        }
+    };
+
+    Ok(Command::Simulate {
+        strategies,
+        difficulties,
+        games,
+        seed,
+    })
+}
+
+fn parse_serve(args: &mut Args) -> Result<Command, CliError> {
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut config = ServerConfig::default();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--addr" => addr = args.value("--addr")?.to_string(),
+            "--rounds" => config.rounds = Some(args.parsed("--rounds")?),
+            "--seed" => config.seed = Some(args.parsed("--seed")?),
+            // Races have no attempt limit, so only the range of a preset is used.
+            "--difficulty" => config.range = args.parsed::<Difficulty>("--difficulty")?.range(),
+            "--range" => config.range = args.range("--range")?,
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    Ok(Command::Serve { addr, config })
+}
+
+fn parse_connect(args: &mut Args) -> Result<Command, CliError> {
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut name = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--addr" => addr = args.value("--addr")?.to_string(),
+            "--name" => name = Some(args.value("--name")?.to_string()),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    Ok(Command::Connect { addr, name })
+}
+
+fn parse_convert(args: &mut Args) -> Result<Command, CliError> {
+    let mut literal = None;
+    let mut unsuffixed = IntType::I32;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--type" => unsuffixed = args.parsed("--type")?,
+            // Negative literals start with a single `-`, so only `--` marks a flag.
+            other if literal.is_none() && !other.starts_with("--") => {
+                literal = Some(other.to_string())
+            }
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    let literal = literal.ok_or(CliError::MissingLiteral)?;
+    // Checked here, so that a bad literal is a usage error like any other.
+    literals::convert(&literal, unsuffixed)
+        .map_err(|error| CliError::InvalidLiteral(literal.clone(), error))?;
+    Ok(Command::Convert {
+        literal,
+        unsuffixed,
+    })
+}
+
+fn parse_overflow(args: &mut Args) -> Result<Command, CliError> {
+    let mut ty = None;
+    let mut json = false;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--type" => ty = Some(args.parsed("--type")?),
+            "--json" => json = true,
+            // Negative operands start with a single `-`.
+            other if !other.starts_with("--") => positional.push(other),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    let invalid = |error: ExploreError| CliError::InvalidOptions(error.to_string());
+    let (op, lhs, rhs) = match positional[..] {
+        [op, lhs] => (op, lhs, None),
+        [op, lhs, rhs] => (op, lhs, Some(rhs)),
+        _ => {
+            return Err(CliError::InvalidOptions(
+                "'overflow' needs an operation and its operands".to_string(),
+            ))
+        }
+    };
+    let op: Op = op.parse().map_err(invalid)?;
+    let ty = ty.unwrap_or_else(|| overflow::infer_type(op, lhs, rhs));
+    let report = overflow::explore(ty, op, lhs, rhs).map_err(invalid)?;
+    Ok(Command::Overflow { report, json })
+}
+
+fn parse_rules(args: &mut Args) -> Result<Command, CliError> {
+    let mut rules = RuleSet::fizz_buzz();
+    let mut range = 1..=100;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            // Like --lang, a file that cannot be used is a bad value.
+            "--file" => {
+                let path = args.value("--file")?;
+                rules = RuleSet::load(path).map_err(|error| CliError::InvalidValue {
+                    flag: "--file",
+                    value: path.to_string(),
+                    reason: error.to_string(),
+                })?;
+            }
+            "--range" => range = args.range("--range")?,
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    Ok(Command::Rules { rules, range })
+}
+
+fn parse_sequence(args: &mut Args) -> Result<Command, CliError> {
+    let mut positional = Vec::new();
+    let mut start = None;
+    let mut big = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--start" => start = Some(args.value("--start")?),
+            "--big" => big = true,
+            other if !other.starts_with("--") => positional.push(other),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+
+    let invalid = |error: SequenceError| CliError::InvalidOptions(error.to_string());
+    let (sequence, position) = match positional[..] {
+        [sequence, position] => (sequence.parse::<Sequence>().map_err(invalid)?, position),
+        _ => {
+            return Err(CliError::InvalidOptions(
+                "'sequence' needs a sequence and a term or range of terms".to_string(),
+            ))
+        }
+    };
+    // A single term is a range of one.
+    let indexes = match position.parse::<u32>() {
+        Ok(index) => index..=index,
+        Err(_) => numbers::parse_range(position).map_err(|error| CliError::InvalidValue {
+            flag: "sequence",
+            value: position.to_string(),
+            reason: error.to_string(),
+        })?,
+    };
+    let start = match (sequence, start) {
+        (Sequence::Collatz, Some(start)) => start,
+        (Sequence::Collatz, None) => return Err(CliError::MissingValue("--start")),
+        (_, Some(_)) => {
+            return Err(CliError::InvalidOptions(
+                "--start is only for collatz".to_string(),
+            ))
+        }
+        (_, None) => "1",
+    };
+
+    Ok(Command::Sequence {
+        sequence,
+        start: start.to_string(),
+        indexes,
+        big,
+    })
+}
+
+fn parse_inspect_char(args: &mut Args) -> Result<Command, CliError> {
+    let mut chars = Vec::new();
+    while let Some(arg) = args.next() {
+        let inspected = chars::inspect(arg).map_err(|error| CliError::InvalidValue {
+            flag: "inspect-char",
+            value: arg.to_string(),
+            reason: error.to_string(),
+        })?;
+        chars.extend(inspected);
+    }
+    if chars.is_empty() {
+        return Err(CliError::InvalidOptions(
+            "'inspect-char' needs some text or code points".to_string(),
+        ));
+    }
+    Ok(Command::InspectChar(chars))
+}
+
+fn parse_units(args: &mut Args) -> Result<Command, CliError> {
+    let mut words = Vec::new();
+    let mut to: Option<Unit> = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--to" => to = Some(args.parsed("--to")?),
+            // Negative temperatures start with a single `-`, and "5 km" may
+            // come as two arguments.
+            other if !other.starts_with("--") => words.push(other),
+            other => return Err(CliError::UnexpectedArgument(other.to_string())),
+        }
+    }
+    if words.is_empty() {
+        return Err(CliError::InvalidOptions(
+            "'units' needs a quantity like 98.6F or 5km".to_string(),
+        ));
+    }
+
+    let text = words.join(" ");
+    let quantity: Quantity = text.parse().map_err(|error: UnitsError| CliError::InvalidValue {
+        flag: "units",
+        value: text.clone(),
+        reason: error.to_string(),
+    })?;
+    let quantities = match to {
+        Some(unit) => {
+            let converted = quantity.to(unit).map_err(|error| CliError::InvalidValue {
+                flag: "--to",
+                value: unit.to_string(),
+                reason: error.to_string(),
+            })?;
+            vec![quantity, converted]
+        }
+        None => quantity.conversions(),
+    };
+    Ok(Command::Units(quantities))
+}
+
+fn parse_trace(args: &mut Args) -> Result<Command, CliError> {
+    // Without the tracer every count would be zero, which looks like an answer.
+    if !alloc_trace::is_installed() {
+        return Err(CliError::InvalidOptions(
+            "'trace' needs the allocation tracer: cargo run --features alloc-trace -- trace 04"
+                .to_string(),
+        ));
+    }
+    let ids: Vec<String> = std::iter::from_fn(|| args.next()).map(str::to_string).collect();
+    if ids.is_empty() {
+        return Err(CliError::InvalidOptions(
+            "'trace' needs one or more chapter numbers".to_string(),
+        ));
+    }
+    Ok(Command::Trace(ids))
+}
+
+fn write_sequence<N>(
+    out: &mut dyn Write,
+    sequence: Sequence,
+    start: &str,
+    indexes: RangeInclusive<u32>,
+) -> Result<(), CliError>
+where
+    N: Natural + FromStr + 'static,
+    N::Err: fmt::Display,
+{
+    let start: N = start.parse().map_err(|error: N::Err| CliError::InvalidValue {
+        flag: "--start",
+        value: start.to_string(),
+        reason: error.to_string(),
+    })?;
+    for term in sequence.indexed(start, indexes) {
+        let term = term.map_err(|error| match error {
+            SequenceError::TooLarge { .. } => {
+                CliError::InvalidOptions(format!("{}; add --big for numbers of any size", error))
+            }
+            error => CliError::InvalidOptions(error.to_string()),
+        })?;
+        writeln!(out, "{term}")?;
+    }
+    Ok(())
+}
+
+/// Runs `command` with chapters from `registry`. Games read their answers
+/// from `input`; everything is printed to `out`.
+pub fn execute(
+    registry: &Registry,
+    command: Command,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    match command {
+        Command::List => {
+            for chapter in registry.iter() {
+                writeln!(
+                    out,
+                    "{}  {}  [{}]",
+                    chapter.id(),
+                    chapter_title(chapter),
+                    chapter.topics().join(", ")
+                )?;
+            }
+        }
+        Command::Run(id) => match registry.find(&id) {
+            Some(chapter) => run_chapter(chapter, out)?,
+            None => return Err(CliError::UnknownChapter(id)),
+        },
+        Command::RunAll => {
+            for chapter in registry.iter() {
+                run_chapter(chapter, out)?;
+            }
+        }
+        Command::Play {
+            difficulty,
+            reveal_secret,
+            score_file,
+            replay_dir,
+            hints,
+        } => {
+            let difficulty = match difficulty {
+                Some(difficulty) => difficulty,
+                None => Difficulty::prompt(input, out)?,
+            };
+            let settings = GameSettings {
+                difficulty,
+                reveal_secret,
+                score_file,
+                replay_dir,
+                hints,
+            };
+            part02::guess_game_v3(input, out, &mut rand::thread_rng(), &settings)?;
+        }
+        Command::Scores {
+            score_file,
+            range,
+            top,
+            reset,
+            scoring,
+            since,
+        } => {
+            let scoring = guessing::scoring_by_name(&scoring).expect("checked by parse");
+            show_scores(&score_file, scoring, range, top, reset, since, out)?
+        }
+        Command::Replay(path) => replay(&path, out)?,
+        Command::BullsAndCows {
+            rules,
+            max_attempts,
+            solve: false,
+            ..
+        } => {
+            let mut rng = rand::thread_rng();
+            guessing::play_bulls_and_cows(input, out, &mut rng, rules, max_attempts)?
+        }
+        Command::BullsAndCows {
+            rules,
+            solve: true,
+            secret,
+            ..
+        } => {
+            let game = match secret {
+                Some(secret) => CodeGame::with_secret(secret, rules),
+                None => CodeGame::new(&mut rand::thread_rng(), rules),
+            };
+            let solver = MinimaxSolver::new(rules).expect("checked by parse");
+            guessing::watch_solver(out, solver, game)?;
+        }
+        Command::Hangman {
+            words,
+            word,
+            letters,
+            max_misses,
+        } => {
+            let word = match word {
+                Some(word) => word.trim().to_string(),
+                None => {
+                    let list = match words {
+                        Some(path) => WordList::load(path)?,
+                        None => WordList::default(),
+                    };
+                    let word = list.choose(&mut rand::thread_rng());
+                    word.expect("word lists are never empty").to_string()
+                }
+            };
+            let game = HangmanGame::new(&word, letters)
+                .expect("checked by parse, and word lists skip words without letters")
+                .with_max_misses(Some(max_misses));
+            guessing::play_hangman(input, out, game)?;
+        }
+        Command::Reverse { range } => guessing::play_reverse(input, out, range)?,
+        Command::Simulate {
+            strategies,
+            difficulties,
+            games,
+            seed,
+        } => {
+            for difficulty in &difficulties {
+                for name in &strategies {
+                    // Parsing only lets known names through. The bots get a
+                    // seed of their own: with the one used for the secrets,
+                    // the random bot would pick every secret first time.
+                    let mut strategy = guessing::strategy_by_name(name, seed.wrapping_add(1))
+                        .expect("strategy names are checked by parse");
+                    let report = guessing::simulate(strategy.as_mut(), difficulty, games, seed);
+                    writeln!(out, "{}", report)?;
+                }
+            }
+        }
+        Command::Serve { addr, config } => {
+            let server = Server::bind(addr.as_str(), config)?;
+            // Scripts that start the server on port 0 read the real address
+            // from this line.
+            writeln!(out, "Listening on {}", server.local_addr()?)?;
+            out.flush()?;
+            server.run()?;
+        }
+        Command::Connect { addr, name } => {
+            let mut client = Client::connect(addr.as_str())?;
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    writeln!(out, "Enter your name:")?;
+                    let mut name = String::new();
+                    input.read_line(&mut name)?;
+                    name.trim().to_string()
+                }
+            };
+            net::play_online(&mut client, &name, input, out)?;
+        }
+        Command::Convert {
+            literal,
+            unsuffixed,
+        } => {
+            let value = literals::convert(&literal, unsuffixed).expect("checked by parse");
+            show_conversion(&value, out)?;
+        }
+        Command::Overflow { report, json: true } => writeln!(out, "{}", report.to_json())?,
+        Command::Overflow { report, .. } => writeln!(out, "{}", report)?,
+        Command::Layout => {
+            for (index, section) in layout::report().iter().enumerate() {
+                if index > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "{section}")?;
+            }
+        }
+        Command::Trace(ids) => {
+            for (index, id) in ids.into_iter().enumerate() {
+                let chapter = registry
+                    .find(&id)
+                    .ok_or_else(|| CliError::UnknownChapter(id.clone()))?;
+                if index > 0 {
+                    writeln!(out)?;
+                }
+                let title = chapter_title(chapter);
+                let banner =
+                    i18n::format("chapter.banner", &[("id", &chapter.id()), ("title", &title)]);
+                writeln!(out, "{banner}")?;
+                if chapter.interactive() {
+                    writeln!(out, "{}", i18n::text("trace.interactive"))?;
+                    continue;
+                }
+                let mut timeline = Timeline::new();
+                chapter.trace(&mut timeline)?;
+                writeln!(out, "{timeline}")?;
+            }
+        }
+        Command::Units(quantities) => {
+            for (index, quantity) in quantities.iter().enumerate() {
+                if index == 0 {
+                    writeln!(out, "{quantity}")?;
+                } else {
+                    writeln!(out, "= {quantity}")?;
+                }
+            }
+        }
+        Command::InspectChar(chars) => {
+            for info in chars {
+                writeln!(out, "{info}")?;
+            }
+        }
+        Command::Sequence {
+            sequence,
+            start,
+            indexes,
+            big: true,
+        } => write_sequence::<BigUint>(out, sequence, &start, indexes)?,
+        Command::Sequence {
+            sequence,
+            start,
+            indexes,
+            big: false,
+        } => write_sequence::<u64>(out, sequence, &start, indexes)?,
+        Command::Rules { rules, range } => {
+            for label in rules.run(range) {
+                writeln!(out, "{label}")?;
+            }
+        }
+        Command::Help => writeln!(out, "{}", USAGE)?,
+    }
+    Ok(())
+}
+
+fn show_scores(
+    score_file: &Path,
+    scoring: Box<dyn ScoringModel>,
+    range: Option<RangeInclusive<u32>>,
+    top: usize,
+    reset: bool,
+    since: Option<Span>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut table = ScoreTable::load_with(score_file, scoring)?;
+    if table.skipped_lines() > 0 {
+        let args: &[(&str, &dyn fmt::Display)] =
+            &[("count", &table.skipped_lines()), ("path", &score_file.display())];
+        writeln!(out, "{}", i18n::format("scores.skipped", args))?;
+    }
+
+    if reset {
+        table.reset(range.as_ref())?;
+        match range {
+            Some(range) => {
+                let args: &[(&str, &dyn fmt::Display)] =
+                    &[("start", range.start()), ("end", range.end())];
+                writeln!(out, "{}", i18n::format("scores.removed-range", args))?
+            }
+            None => writeln!(out, "{}", i18n::text("scores.removed-all"))?,
+        }
+        return Ok(());
+    }
+
+    let ranges = match range {
+        Some(range) => vec![range],
+        None => table.ranges(),
+    };
+    if ranges.is_empty() {
+        writeln!(out, "{}", i18n::text("scores.none"))?;
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or(0);
+    for range in ranges {
+        let args: &[(&str, &dyn fmt::Display)] = &[("start", range.start()), ("end", range.end())];
+        writeln!(out, "{}", i18n::format("scores.range", args))?;
+        let mut scores = table.top(&range, MAX_SCORES_PER_RANGE);
+        if let Some(since) = since {
+            scores.retain(|score| score.age(now) <= since);
+        }
+        scores.truncate(top);
+        if scores.is_empty() {
+            writeln!(out, "    {}", i18n::text("scores.range-empty"))?;
+        }
+        for (rank, score) in scores.iter().enumerate() {
+            let points = table.scoring().points(score);
+            let word = i18n::text("scores.points");
+            writeln!(out, "{:>3}. {:>5} {}  {}", rank + 1, points, word, score)?;
+        }
+    }
+    Ok(())
+}
+
+fn show_conversion(value: &Value, out: &mut dyn Write) -> io::Result<()> {
+    let ty = value.ty;
+    let min = if ty.is_signed() {
+        format!("-{}", ty.min_magnitude())
+    } else {
+        String::from("0")
+    };
+    writeln!(
+        out,
+        "{value}: {ty} ({} bits, from {min} to {})",
+        ty.bits(),
+        ty.max()
+    )?;
+    for base in Base::ALL {
+        writeln!(out, "{:<8} {}", base.name(), value.in_base(base))?;
+    }
+    writeln!(out, "{:<8} {}", "bits", value.bit_pattern())?;
+    Ok(())
+}
+
+fn replay(path: &Path, out: &mut dyn Write) -> Result<(), CliError> {
+    let session = Session::load(path).map_err(CliError::Replay)?;
+    let start = i18n::plural(
+        "replay.start",
+        session.guesses.len() as u64,
+        &[
+            ("start", session.range.start()),
+            ("end", session.range.end()),
+            ("seed", &session.seed),
+        ],
+    );
+    writeln!(out, "{start}")?;
+    let game = session
+        .replay()
+        .map_err(|mismatch| CliError::Replay(mismatch.into()))?;
+
+    let result = i18n::text(if game.is_won() {
+        "replay.won"
+    } else if game.is_lost() {
+        "replay.lost"
+    } else {
+        "replay.unfinished"
+    });
+    let checked = i18n::plural(
+        "replay.checked",
+        u64::from(game.attempts()),
+        &[("result", &result)],
+    );
+    writeln!(out, "{checked}")?;
+    Ok(())
+}
+
+impl Command {
+    // Whether the command reads from the `input` given to `execute`.
+    fn reads_input(&self) -> bool {
+        matches!(
+            self,
+            Command::Play { .. }
+                | Command::BullsAndCows { solve: false, .. }
+                | Command::Hangman { .. }
+                | Command::Reverse { .. }
+                | Command::Connect { .. }
+        )
+    }
+}
+
+// `--lang <code>` applies to every command, so it comes before the command
+// and is taken off the arguments before they are parsed.
+fn split_lang(args: &[String]) -> Result<(Option<&str>, &[String]), CliError> {
+    match args {
+        [flag, lang, rest @ ..] if flag == "--lang" => Ok((Some(lang.as_str()), rest)),
+        [flag] if flag == "--lang" => Err(CliError::MissingValue("--lang")),
+        _ => Ok((None, args)),
+    }
+}
+
+fn set_lang(lang: &str) -> Result<(), CliError> {
+    let messages = Messages::builtin_or_load(DEFAULT_LOCALE_DIR, lang).map_err(|error| {
+        CliError::InvalidValue {
+            flag: "--lang",
+            value: lang.to_string(),
+            reason: format!("{} (looked in {}/)", error, DEFAULT_LOCALE_DIR),
+        }
+    })?;
+    i18n::set_messages(messages);
+    Ok(())
+}
+
+/// Returns the process exit code.
+pub fn main(registry: &Registry, args: &[String]) -> i32 {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = split_lang(args).and_then(|(lang, args)| {
+        if let Some(lang) = lang {
+            set_lang(lang)?;
+        }
+        parse(args)
+    });
+    let result = result.and_then(|command| {
+        // Interactive chapters lock stdin themselves, so it is only locked here
+        // for commands that read it through `execute`.
+        if command.reads_input() {
+            execute(registry, command, &mut io::stdin().lock(), &mut out)
+        } else {
+            execute(registry, command, &mut io::empty(), &mut out)
+        }
+    });
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            let code = error.exit_code();
+            if code == 2 {
+                eprintln!("error: {}\n\n{}", error, USAGE);
+            } else {
+                eprintln!("error: {}", error);
+            }
+            code
+        }
+    }
+}
+
+fn run_chapter(chapter: &dyn Chapter, out: &mut dyn Write) -> io::Result<()> {
+    let title = chapter_title(chapter);
+    let banner = i18n::format("chapter.banner", &[("id", &chapter.id()), ("title", &title)]);
+    writeln!(out, "{banner}")?;
+    chapter.run(out)
+}
+
+// The title in the current language, if the catalog has it.
+fn chapter_title(chapter: &dyn Chapter) -> String {
+    let id = format!("chapter.{}.title", chapter.id());
+    let title = i18n::messages().get(&id).map(str::to_string);
+    title.unwrap_or_else(|| chapter.title().to_string())
+}
@@ -0,0 +1,195 @@
+//! Lengths of time in whole seconds, written the way people write them:
+//! `3h`, `1h30m` or `2d4h`. Arithmetic is checked.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A length of time in whole seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    secs: u64,
+}
+
+// The units spans are written in, largest first.
+const UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+impl Span {
+    pub const ZERO: Span = Span::from_secs(0);
+    pub const SECOND: Span = Span::from_secs(1);
+    pub const MINUTE: Span = Span::from_mins(1);
+    pub const HOUR: Span = Span::from_hours(1);
+    pub const DAY: Span = Span::from_days(1);
+    pub const WEEK: Span = Span::from_days(7);
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self { secs }
+    }
+
+    /// Panics if the span does not fit in a `u64` of seconds, like
+    /// [`Span::from_hours`] and [`Span::from_days`]. In a constant, that is a
+    /// compile error.
+    pub const fn from_mins(mins: u64) -> Self {
+        Self::from_units(mins, 60)
+    }
+
+    pub const fn from_hours(hours: u64) -> Self {
+        Self::from_units(hours, 3_600)
+    }
+
+    pub const fn from_days(days: u64) -> Self {
+        Self::from_units(days, 86_400)
+    }
+
+    // Checked, so that a span too long panics in release builds as well
+    // rather than wrapping around.
+    const fn from_units(count: u64, unit_secs: u64) -> Self {
+        match count.checked_mul(unit_secs) {
+            Some(secs) => Self::from_secs(secs),
+            None => panic!("the span does not fit in a u64 of seconds"),
+        }
+    }
+
+    pub const fn as_secs(self) -> u64 {
+        self.secs
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.secs == 0
+    }
+
+    pub fn checked_add(self, other: Span) -> Option<Span> {
+        self.secs.checked_add(other.secs).map(Span::from_secs)
+    }
+
+    /// `None` if `other` is the longer span.
+    pub fn checked_sub(self, other: Span) -> Option<Span> {
+        self.secs.checked_sub(other.secs).map(Span::from_secs)
+    }
+
+    pub fn checked_mul(self, times: u64) -> Option<Span> {
+        self.secs.checked_mul(times).map(Span::from_secs)
+    }
+}
+
+impl From<Span> for Duration {
+    fn from(span: Span) -> Self {
+        Duration::from_secs(span.secs)
+    }
+}
+
+/// Drops the fraction of a second.
+impl From<Duration> for Span {
+    fn from(duration: Duration) -> Self {
+        Span::from_secs(duration.as_secs())
+    }
+}
+
+// Every unit that is not zero, largest first: 93_600 seconds is "1d2h".
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.secs == 0 {
+            return write!(f, "0s");
+        }
+        let mut left = self.secs;
+        for (unit, secs) in UNITS {
+            if left >= secs {
+                write!(f, "{}{}", left / secs, unit)?;
+                left %= secs;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSpanError {
+    Empty,
+    /// A number with no unit after it, like the `30` in `1h30`.
+    MissingUnit,
+    /// A unit with no number before it.
+    MissingNumber(char),
+    UnknownUnit(char),
+    /// Units must be written largest first, each at most once.
+    OutOfOrder(char),
+    /// Longer than a `u64` of seconds.
+    TooLong,
+}
+
+impl fmt::Display for ParseSpanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSpanError::Empty => write!(f, "the duration is empty"),
+            ParseSpanError::MissingUnit => {
+                write!(f, "every number needs a unit: d, h, m or s")
+            }
+            ParseSpanError::MissingNumber(unit) => {
+                write!(f, "'{}' needs a number before it", unit)
+            }
+            ParseSpanError::UnknownUnit(unit) => {
+                write!(f, "unknown unit '{}', expected d, h, m or s", unit)
+            }
+            ParseSpanError::OutOfOrder(unit) => write!(
+                f,
+                "'{}' comes too late: write units largest first, each once",
+                unit
+            ),
+            ParseSpanError::TooLong => write!(f, "the duration is too long"),
+        }
+    }
+}
+
+impl std::error::Error for ParseSpanError {}
+
+// Numbers followed by units, like "2d4h" or "1h 30m". A number may be larger
+// than the next unit up, as in "90s".
+impl FromStr for Span {
+    type Err = ParseSpanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(ParseSpanError::Empty);
+        }
+
+        let mut total = Span::ZERO;
+        // Units still allowed: only those smaller than the last one used.
+        let mut units = &UNITS[..];
+        let mut number: Option<u64> = None;
+        for c in text.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                let so_far = number.unwrap_or(0);
+                number = Some(
+                    so_far
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(u64::from(digit)))
+                        .ok_or(ParseSpanError::TooLong)?,
+                );
+                continue;
+            }
+            if c.is_whitespace() && number.is_none() {
+                continue;
+            }
+            let Some(position) = units.iter().position(|(unit, _)| *unit == c) else {
+                return Err(if UNITS.iter().any(|(unit, _)| *unit == c) {
+                    ParseSpanError::OutOfOrder(c)
+                } else if c.is_whitespace() {
+                    ParseSpanError::MissingUnit
+                } else {
+                    ParseSpanError::UnknownUnit(c)
+                });
+            };
+            let count = number.take().ok_or(ParseSpanError::MissingNumber(c))?;
+            let secs = units[position].1;
+            total = Span::from_secs(secs)
+                .checked_mul(count)
+                .and_then(|span| total.checked_add(span))
+                .ok_or(ParseSpanError::TooLong)?;
+            units = &units[position + 1..];
+        }
+        if number.is_some() {
+            return Err(ParseSpanError::MissingUnit);
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,234 @@
+//! The guessing games, without any input or output: the number guessing game
+//! from chapter 02 and its variants (digit codes, hangman, the reverse game),
+//! hints, scores, replays, bots and the [`net`] race. Front-ends play them
+//! through the [`Game`] trait and [`play_turns`].
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::i18n;
+
+pub mod net;
+
+mod bulls_and_cows;
+mod difficulty;
+mod game;
+mod hangman;
+mod hints;
+mod replay;
+mod reverse;
+mod scores;
+mod simulation;
+mod strategy;
+
+pub use bulls_and_cows::{
+    play_bulls_and_cows, watch_solver, Code, CodeGame, CodeRules, Hint, InvalidRules,
+    MinimaxSolver, MAX_SOLVER_CODES,
+};
+pub use difficulty::{Difficulty, GameSettings, ParseDifficultyError};
+pub use game::{play_turns, Game, InvalidGuess, OnTurn};
+pub use hangman::{
+    play_hangman, HangmanGame, Letters, NoLetters, ParseLettersError, Progress, WordList,
+    DEFAULT_MAX_MISSES, DEFAULT_WORDS,
+};
+pub use hints::{Clue, HintSettings, HintedGame, ParseQuestionError, Proximity, Question};
+pub use replay::{
+    Mismatch, RecordedGuess, Recorder, ReplayError, Session, DEFAULT_REPLAY_DIR,
+};
+pub use reverse::{play_reverse, Answer, Bound, Contradiction, ParseAnswerError, ReverseGame};
+pub use scores::{
+    scoring_by_name, GuessCost, RangeBits, Score, ScoreTable, ScoringModel, DEFAULT_SCORE_FILE,
+    MAX_SCORES_PER_RANGE, SCORING_MODELS,
+};
+pub use simulation::{simulate, SimulationReport, GIVE_UP_AFTER};
+pub use strategy::{
+    strategy_by_name, BinarySearch, HumanLike, RandomGuess, Strategy, STRATEGIES,
+};
+
+/// The range the secret number is picked from unless told otherwise.
+pub const DEFAULT_RANGE: RangeInclusive<u32> = 0..=100;
+
+/// How the game reacts to a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win,
+    /// The guess was wrong and it was the last attempt allowed.
+    Lost,
+    /// The input was not a number. It does not count as an attempt.
+    Invalid,
+}
+
+impl From<Ordering> for Outcome {
+    // `guess.cmp(&secret)`
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+}
+
+// What part02 prints for each outcome, in the language of the current
+// thread (see `crate::i18n`).
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let id = match self {
+            Outcome::TooSmall => "outcome.too-small",
+            Outcome::TooBig => "outcome.too-big",
+            Outcome::Win => "outcome.win",
+            Outcome::Lost => "outcome.lost",
+            Outcome::Invalid => "outcome.invalid",
+        };
+        write!(f, "{}", i18n::text(id))
+    }
+}
+
+/// The number guessing game of chapter 02: a secret number, the range it was
+/// picked from, and the guesses made so far.
+#[derive(Debug, Clone)]
+pub struct GuessingGame {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    won: bool,
+}
+
+impl GuessingGame {
+    /// A game with a secret number from [`DEFAULT_RANGE`].
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_range(rng, DEFAULT_RANGE)
+    }
+
+    /// A game with a secret number picked uniformly from `range`.
+    pub fn with_range<R: Rng + ?Sized>(rng: &mut R, range: RangeInclusive<u32>) -> Self {
+        let secret = rng.gen_range(range.clone());
+        Self::with_secret(secret, range)
+    }
+
+    /// A game with the range and attempt limit of `difficulty`.
+    pub fn with_difficulty<R: Rng + ?Sized>(rng: &mut R, difficulty: &Difficulty) -> Self {
+        Self::with_range(rng, difficulty.range()).with_max_attempts(difficulty.max_attempts())
+    }
+
+    /// A game with a known secret, e.g. to replay a game or test a front-end.
+    pub fn with_secret(secret: u32, range: RangeInclusive<u32>) -> Self {
+        Self {
+            secret,
+            range,
+            max_attempts: None,
+            attempts: 0,
+            won: false,
+        }
+    }
+
+    /// Limits the number of valid guesses; `None` allows any number of them.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Handles a line of user input. Surrounding whitespace, including the
+    /// trailing newline left by `read_line`, is ignored.
+    pub fn guess(&mut self, input: &str) -> Outcome {
+        // The parse method on strings converts a string to another type. It
+        // returns a Result, because the text might not be a number at all;
+        // rather than crashing, such input is reported as Invalid.
+        match input.trim().parse::<u32>() {
+            Ok(number) => self.guess_number(number),
+            Err(_) => Outcome::Invalid,
+        }
+    }
+
+    /// Handles a guess that is already a number. Once the game is over,
+    /// further guesses are not counted and keep returning [`Outcome::Win`] or
+    /// [`Outcome::Lost`].
+    pub fn guess_number(&mut self, number: u32) -> Outcome {
+        if self.won {
+            return Outcome::Win;
+        }
+        if self.is_lost() {
+            return Outcome::Lost;
+        }
+        self.attempts += 1;
+        let outcome = Outcome::from(number.cmp(&self.secret));
+        self.won = outcome == Outcome::Win;
+        if self.is_lost() {
+            return Outcome::Lost;
+        }
+        outcome
+    }
+
+    /// The number to guess.
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    /// The range the secret was picked from.
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    /// Number of valid guesses made so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Valid guesses left before the game is lost, or `None` without a limit.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Whether a guess hit the secret.
+    pub fn is_won(&self) -> bool {
+        self.won
+    }
+
+    /// Whether every attempt was used up without hitting the secret.
+    pub fn is_lost(&self) -> bool {
+        !self.won && self.attempts_left() == Some(0)
+    }
+
+    /// Whether the game is won or lost, so further guesses do not count.
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
+
+impl Game for GuessingGame {
+    type Feedback = Outcome;
+
+    fn play(&mut self, input: &str) -> Result<Outcome, InvalidGuess> {
+        match self.guess(input) {
+            Outcome::Invalid => Err(InvalidGuess::new(Outcome::Invalid.to_string())),
+            outcome => Ok(outcome),
+        }
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    fn attempts_left(&self) -> Option<u32> {
+        GuessingGame::attempts_left(self)
+    }
+
+    fn is_won(&self) -> bool {
+        self.won
+    }
+
+    fn is_lost(&self) -> bool {
+        GuessingGame::is_lost(self)
+    }
+
+    fn solution(&self) -> String {
+        i18n::format("game.solution", &[("secret", &self.secret)])
+    }
+}
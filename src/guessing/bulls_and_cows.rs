@@ -0,0 +1,481 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use super::{play_turns, Game, InvalidGuess};
+use crate::i18n;
+
+/// The solver looks at every code for every guess, so it refuses rules with
+/// more codes than this.
+pub const MAX_SOLVER_CODES: usize = 10_000;
+
+/// The shape of the secret code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeRules {
+    length: usize,
+    digits: u8,
+    repeats: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRules(String);
+
+impl fmt::Display for InvalidRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidRules {}
+
+impl CodeRules {
+    /// Codes of `length` digits from 0 to `digits - 1`; `repeats` allows the
+    /// same digit more than once. `digits` is at most 10 so that every digit
+    /// is typed as one character.
+    pub fn new(length: usize, digits: u8, repeats: bool) -> Result<Self, InvalidRules> {
+        if !(2..=10).contains(&digits) {
+            return Err(InvalidRules(format!(
+                "codes use between 2 and 10 different digits, not {}",
+                digits
+            )));
+        }
+        if !(1..=9).contains(&length) {
+            return Err(InvalidRules(format!(
+                "codes are between 1 and 9 digits long, not {}",
+                length
+            )));
+        }
+        if !repeats && length > usize::from(digits) {
+            return Err(InvalidRules(format!(
+                "a code of {} different digits needs at least {} digits to pick from",
+                length, length
+            )));
+        }
+        Ok(Self {
+            length,
+            digits,
+            repeats,
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// How many different codes there are.
+    pub fn code_count(&self) -> usize {
+        let digits = usize::from(self.digits);
+        if self.repeats {
+            digits.pow(self.length as u32)
+        } else {
+            (digits - self.length + 1..=digits).product()
+        }
+    }
+
+    /// Every code, smallest first.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let mut codes = Vec::with_capacity(self.code_count());
+        let mut digits = vec![0; self.length];
+        loop {
+            if self.repeats || !has_repeats(&digits) {
+                codes.push(Code(digits.clone()));
+            }
+            // Count up, the last digit fastest.
+            let mut position = self.length;
+            loop {
+                if position == 0 {
+                    return codes;
+                }
+                position -= 1;
+                digits[position] += 1;
+                if digits[position] < self.digits {
+                    break;
+                }
+                digits[position] = 0;
+            }
+        }
+    }
+
+    /// A code picked uniformly at random.
+    pub fn random_code<R: Rng + ?Sized>(&self, rng: &mut R) -> Code {
+        if self.repeats {
+            Code(
+                (0..self.length)
+                    .map(|_| rng.gen_range(0..self.digits))
+                    .collect(),
+            )
+        } else {
+            let mut digits: Vec<u8> = (0..self.digits).collect();
+            digits.shuffle(rng);
+            digits.truncate(self.length);
+            Code(digits)
+        }
+    }
+
+    /// Reads a code such as "0427", checking it against the rules. Spaces
+    /// around it, including a trailing newline, are ignored.
+    pub fn parse(&self, input: &str) -> Result<Code, InvalidGuess> {
+        let expected = || {
+            let args: &[(&str, &dyn fmt::Display)] =
+                &[("length", &self.length), ("max", &(self.digits - 1))];
+            InvalidGuess::new(i18n::format("code.expected", args))
+        };
+        let input = input.trim();
+        let digits = input
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) if digit < u32::from(self.digits) => Ok(digit as u8),
+                _ => Err(expected()),
+            })
+            .collect::<Result<Vec<u8>, InvalidGuess>>()?;
+        if digits.len() != self.length {
+            return Err(expected());
+        }
+        if !self.repeats && has_repeats(&digits) {
+            return Err(InvalidGuess::new(i18n::format(
+                "code.repeated",
+                &[("code", &input)],
+            )));
+        }
+        Ok(Code(digits))
+    }
+}
+
+impl Default for CodeRules {
+    /// Classic bulls and cows: four different digits from 0 to 9.
+    fn default() -> Self {
+        Self {
+            length: 4,
+            digits: 10,
+            repeats: false,
+        }
+    }
+}
+
+impl fmt::Display for CodeRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let id = if self.repeats {
+            "code.rules.repeats"
+        } else {
+            "code.rules.different"
+        };
+        let args: &[(&str, &dyn fmt::Display)] =
+            &[("length", &self.length), ("max", &(self.digits - 1))];
+        write!(f, "{}", i18n::format(id, args))
+    }
+}
+
+fn has_repeats(digits: &[u8]) -> bool {
+    let mut seen = [false; 10];
+    digits
+        .iter()
+        .any(|&digit| std::mem::replace(&mut seen[usize::from(digit)], true))
+}
+
+/// A secret code or a guess at one.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Code(Vec<u8>);
+
+impl Code {
+    pub fn digits(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// How `guess` matches this code.
+    ///
+    /// Every digit of the guess is matched at most once: a `1` guessed twice
+    /// against a code with a single `1` gives one bull or cow, not two.
+    pub fn hint(&self, guess: &Code) -> Hint {
+        let mut bulls = 0;
+        let mut in_code = [0u8; 10];
+        let mut in_guess = [0u8; 10];
+        for (&secret, &guessed) in self.0.iter().zip(&guess.0) {
+            if secret == guessed {
+                bulls += 1;
+            } else {
+                in_code[usize::from(secret)] += 1;
+                in_guess[usize::from(guessed)] += 1;
+            }
+        }
+        let cows = in_code
+            .iter()
+            .zip(&in_guess)
+            .map(|(&code, &guess)| code.min(guess))
+            .sum();
+        Hint { bulls, cows }
+    }
+
+    // True if the digits appear in the order 0, 1, 2, ... the first time they
+    // are used, like 0012 but not 1002. Every code can be turned into exactly
+    // one of these by renaming its digits.
+    fn is_canonical(&self) -> bool {
+        let mut next = 0;
+        for &digit in &self.0 {
+            if digit == next {
+                next += 1;
+            } else if digit > next {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.0 {
+            write!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+/// The feedback for a guess: bulls are right digits in the right place, cows
+/// are right digits in the wrong place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hint {
+    pub bulls: u8,
+    pub cows: u8,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bulls = i18n::plural("code.bulls", u64::from(self.bulls), &[]);
+        let cows = i18n::plural("code.cows", u64::from(self.cows), &[]);
+        write!(f, "{}", i18n::format("code.hint", &[("bulls", &bulls), ("cows", &cows)]))
+    }
+}
+
+/// Bulls and cows, or Mastermind with digits for colours: the number
+/// guessing game with a code instead of a number, and bulls and cows instead
+/// of "too small" and "too big".
+///
+/// ```
+/// use hello_rust::guessing::{CodeGame, CodeRules, Game, Hint};
+///
+/// let rules = CodeRules::default();
+/// let mut game = CodeGame::with_secret(rules.parse("1234").unwrap(), rules);
+/// assert_eq!(game.play("1389\n"), Ok(Hint { bulls: 1, cows: 1 }));
+/// assert!(game.play("1123").is_err());
+/// assert!(game.play("1234").is_ok());
+/// assert!(game.is_won());
+/// assert_eq!(game.attempts(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeGame {
+    rules: CodeRules,
+    secret: Code,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    won: bool,
+}
+
+impl CodeGame {
+    /// A game with a secret code picked at random.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R, rules: CodeRules) -> Self {
+        Self::with_secret(rules.random_code(rng), rules)
+    }
+
+    /// A game with a known secret, which must follow `rules`.
+    pub fn with_secret(secret: Code, rules: CodeRules) -> Self {
+        Self {
+            rules,
+            secret,
+            max_attempts: None,
+            attempts: 0,
+            won: false,
+        }
+    }
+
+    /// Limits the number of valid guesses; `None` allows any number of them.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn rules(&self) -> &CodeRules {
+        &self.rules
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    /// Handles a guess that is already a code. Once the game is over, further
+    /// guesses are not counted.
+    pub fn guess_code(&mut self, guess: &Code) -> Hint {
+        let hint = self.secret.hint(guess);
+        if !self.is_over() {
+            self.attempts += 1;
+            self.won = usize::from(hint.bulls) == self.rules.length;
+        }
+        hint
+    }
+}
+
+impl Game for CodeGame {
+    type Feedback = Hint;
+
+    fn play(&mut self, input: &str) -> Result<Hint, InvalidGuess> {
+        let guess = self.rules.parse(input)?;
+        Ok(self.guess_code(&guess))
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    fn is_won(&self) -> bool {
+        self.won
+    }
+
+    fn is_lost(&self) -> bool {
+        !self.won && self.attempts_left() == Some(0)
+    }
+
+    fn solution(&self) -> String {
+        i18n::format("code.solution", &[("code", &self.secret)])
+    }
+}
+
+/// Plays bulls and cows with Knuth's minimax strategy: every guess is the
+/// code whose worst possible hint leaves the fewest codes to choose from.
+///
+/// Ties go to a code that could still be the secret, then to the smallest
+/// code. With classic Mastermind rules (4 digits from 0 to 5, repeats
+/// allowed) this always finds the code in 5 guesses or fewer.
+#[derive(Debug, Clone)]
+pub struct MinimaxSolver {
+    rules: CodeRules,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl MinimaxSolver {
+    pub fn new(rules: CodeRules) -> Result<Self, InvalidRules> {
+        if rules.code_count() > MAX_SOLVER_CODES {
+            return Err(InvalidRules(format!(
+                "the solver handles at most {} codes, and {} has {}",
+                MAX_SOLVER_CODES,
+                rules,
+                rules.code_count()
+            )));
+        }
+        let all = rules.all_codes();
+        Ok(Self {
+            rules,
+            candidates: all.clone(),
+            all,
+        })
+    }
+
+    /// The codes that agree with every hint so far.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    /// The best guess, or `None` when no code agrees with all the hints.
+    pub fn next_guess(&self) -> Option<Code> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().cloned();
+        }
+
+        // Before the first hint all codes look alike, so only one code per
+        // pattern of repeated digits needs to be tried.
+        let first_guess = self.candidates.len() == self.all.len();
+        let width = self.rules.length + 1;
+        let win = self.rules.length * width;
+        let mut counts = vec![0usize; width * width];
+
+        // (largest group of codes left, not a candidate)
+        let mut best: Option<((usize, bool), &Code)> = None;
+        for guess in &self.all {
+            if first_guess && !guess.is_canonical() {
+                continue;
+            }
+            counts.iter_mut().for_each(|count| *count = 0);
+            let mut worst = 0;
+            for candidate in &self.candidates {
+                let hint = candidate.hint(guess);
+                let count = &mut counts[usize::from(hint.bulls) * width + usize::from(hint.cows)];
+                *count += 1;
+                worst = worst.max(*count);
+                // This guess cannot beat the best one any more.
+                if best.is_some_and(|((best_worst, _), _)| worst > best_worst) {
+                    break;
+                }
+            }
+            let key = (worst, counts[win] == 0);
+            if !best.is_some_and(|(best_key, _)| key >= best_key) {
+                best = Some((key, guess));
+            }
+        }
+        best.map(|(_, guess)| guess.clone())
+    }
+
+    /// Keeps the candidates that would have given `hint` for `guess`.
+    pub fn feedback(&mut self, guess: &Code, hint: Hint) {
+        self.candidates
+            .retain(|candidate| candidate.hint(guess) == hint);
+    }
+}
+
+/// Plays a [`CodeGame`] with guesses read from `input`: a code of digits
+/// instead of a number, with bulls and cows instead of "too small" and "too
+/// big".
+pub fn play_bulls_and_cows(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rng: &mut dyn RngCore,
+    rules: CodeRules,
+    max_attempts: Option<u32>,
+) -> io::Result<()> {
+    let mut game = CodeGame::new(rng, rules).with_max_attempts(max_attempts);
+
+    writeln!(out, "{}", i18n::format("code.intro", &[("rules", &rules)]))?;
+    writeln!(out, "{}", i18n::text("code.explain"))?;
+    play_turns(&mut game, input, out, &mut |_, _, _| Ok(()))
+}
+
+/// The minimax solver plays `game` on its own, printing every guess to `out`.
+pub fn watch_solver(
+    out: &mut dyn Write,
+    mut solver: MinimaxSolver,
+    mut game: CodeGame,
+) -> io::Result<()> {
+    writeln!(out, "{}", i18n::format("solver.code", &[("code", game.secret())]))?;
+
+    while !game.is_won() {
+        let guess = solver
+            .next_guess()
+            .expect("the secret agrees with every hint");
+        let hint = game.guess_code(&guess);
+        solver.feedback(&guess, hint);
+        let args: &[(&str, &dyn fmt::Display)] = &[
+            ("attempt", &game.attempts()),
+            ("guess", &guess),
+            ("hint", &hint),
+            ("left", &solver.candidates().len()),
+        ];
+        writeln!(out, "{}", i18n::format("solver.guess", args))?;
+    }
+
+    let attempts = game.attempts();
+    writeln!(out, "{}", i18n::format("solver.found", &[("attempts", &attempts)]))?;
+    Ok(())
+}
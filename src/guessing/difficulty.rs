@@ -0,0 +1,139 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::HintSettings;
+use crate::i18n;
+
+/// How hard a game is: the range the secret is picked from and how many
+/// guesses the player gets.
+///
+/// The presets leave enough attempts to always win by halving the remaining
+/// range with every guess, but not many more.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    /// 0 to 50, 10 attempts.
+    Easy,
+    /// 0 to 100, 8 attempts.
+    #[default]
+    Normal,
+    /// 0 to 1000, 10 attempts.
+    Hard,
+    /// Any non-empty range; `max_attempts` of `None` means unlimited guesses.
+    Custom {
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    },
+}
+
+impl Difficulty {
+    /// The range the secret is picked from.
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 0..=50,
+            Difficulty::Normal => 0..=100,
+            Difficulty::Hard => 0..=1000,
+            Difficulty::Custom { range, .. } => range.clone(),
+        }
+    }
+
+    /// How many guesses the player gets; `None` for any number of them.
+    pub fn max_attempts(&self) -> Option<u32> {
+        match self {
+            Difficulty::Easy => Some(10),
+            Difficulty::Normal => Some(8),
+            Difficulty::Hard => Some(10),
+            Difficulty::Custom { max_attempts, .. } => *max_attempts,
+        }
+    }
+
+    /// Asks on `out` which difficulty to play until `input` names one of the
+    /// presets. Pressing enter, or running out of input, picks normal.
+    pub fn prompt(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<Self> {
+        loop {
+            write!(out, "{} ", i18n::text("difficulty.prompt"))?;
+            out.flush()?;
+
+            let mut answer = String::new();
+            if input.read_line(&mut answer)? == 0 || answer.trim().is_empty() {
+                return Ok(Difficulty::Normal);
+            }
+            match answer.parse() {
+                Ok(difficulty) => return Ok(difficulty),
+                Err(_) => {
+                    let answer = answer.trim();
+                    writeln!(out, "{}", i18n::format("difficulty.invalid", &[("answer", &answer)]))?
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Normal => write!(f, "normal"),
+            Difficulty::Hard => write!(f, "hard"),
+            Difficulty::Custom {
+                range,
+                max_attempts,
+            } => {
+                write!(f, "custom {}..={}", range.start(), range.end())?;
+                match max_attempts {
+                    Some(max) => write!(f, ", {} attempts", max),
+                    None => write!(f, ", unlimited attempts"),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDifficultyError {
+    UnknownPreset(String),
+}
+
+impl fmt::Display for ParseDifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseDifficultyError::UnknownPreset(name) => write!(
+                f,
+                "unknown difficulty '{}', expected easy, normal or hard",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseDifficultyError {}
+
+// "easy", "normal" or "hard"; custom difficulties are built from a range.
+impl FromStr for Difficulty {
+    type Err = ParseDifficultyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(ParseDifficultyError::UnknownPreset(other.to_string())),
+        }
+    }
+}
+
+/// Everything a front-end needs to set up a game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameSettings {
+    pub difficulty: Difficulty,
+    /// Print the secret number when the game starts. Only meant for debugging.
+    pub reveal_secret: bool,
+    /// Where to record won games; `None` keeps no scores.
+    pub score_file: Option<PathBuf>,
+    /// Directory to write a replay log of the session to; `None` records
+    /// nothing.
+    pub replay_dir: Option<PathBuf>,
+    pub hints: HintSettings,
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::i18n;
+
+/// A line of input that is not a guess. It does not count as an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidGuess(String);
+
+impl InvalidGuess {
+    /// `reason` is shown to the player as it is.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self(reason.into())
+    }
+}
+
+impl fmt::Display for InvalidGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidGuess {}
+
+/// A game where the player keeps guessing until they win or run out of
+/// attempts, like the number guessing game and its digit-code variant.
+pub trait Game {
+    /// What the player is told about a valid guess.
+    type Feedback: fmt::Display;
+
+    /// Takes a line of input, checking that it is a guess first.
+    fn play(&mut self, input: &str) -> Result<Self::Feedback, InvalidGuess>;
+
+    /// Number of valid guesses made so far.
+    fn attempts(&self) -> u32;
+
+    /// Valid guesses left before the game is lost, or `None` without a limit.
+    fn attempts_left(&self) -> Option<u32>;
+
+    fn is_won(&self) -> bool;
+
+    fn is_lost(&self) -> bool;
+
+    fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+
+    /// Told to a player who has lost, e.g. "The secret number was 42".
+    fn solution(&self) -> String;
+
+    /// Asks for the next guess.
+    fn prompt(&self) -> String {
+        i18n::text("guess.prompt")
+    }
+}
+
+/// Called by [`play_turns`] with every line of input, how the game took it,
+/// and the output.
+pub type OnTurn<'a, F> =
+    dyn FnMut(&str, &Result<F, InvalidGuess>, &mut dyn Write) -> io::Result<()> + 'a;
+
+/// Reads guesses from `input` until the game is over or `input` ends.
+///
+/// Every line is passed to `on_turn` along with how the game took it, before
+/// anything is printed about it; front-ends use it to record the game.
+pub fn play_turns<G: Game + ?Sized>(
+    game: &mut G,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    on_turn: &mut OnTurn<'_, G::Feedback>,
+) -> io::Result<()> {
+    // using loop to take multiple user guesses
+    while !game.is_over() {
+        writeln!(out, "{}", game.prompt())?;
+
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            break; // no more input
+        }
+
+        writeln!(out, "{}", i18n::format("guess.echo", &[("guess", &guess)]))?;
+
+        let result = game.play(&guess);
+        on_turn(&guess, &result, out)?;
+
+        let feedback = match result {
+            Ok(feedback) => feedback,
+            // Rather than crashing the program when the input is not a guess,
+            // say why and let the player try again.
+            Err(invalid) => {
+                writeln!(out, "{invalid}")?;
+                continue;
+            }
+        };
+        if game.is_won() {
+            writeln!(out, "{}", i18n::text("guess.win"))?;
+        } else if game.is_lost() {
+            let solution = game.solution();
+            writeln!(out, "{}", i18n::format("guess.lose", &[("solution", &solution)]))?;
+        } else {
+            writeln!(out, "{feedback}")?;
+            if let Some(left) = game.attempts_left() {
+                writeln!(out, "{}", i18n::format("guess.attempts-left", &[("left", &left)]))?;
+            }
+        }
+    }
+    Ok(())
+}
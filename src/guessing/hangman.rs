@@ -0,0 +1,356 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::game::{play_turns, Game, InvalidGuess};
+use crate::i18n;
+
+/// Words to pick from when no word list is given. "Здравствуйте" is the
+/// string from chapter 09 that takes 24 bytes for its 12 letters.
+pub const DEFAULT_WORDS: [&str; 6] = [
+    "ownership",
+    "borrow",
+    "lifetime",
+    "Здравствуйте",
+    "crème brûlée",
+    "naïve",
+];
+
+/// How many wrong guesses a player gets unless told otherwise: one for each
+/// part of the drawing.
+pub const DEFAULT_MAX_MISSES: u32 = 6;
+
+/// What counts as one letter of the word.
+///
+/// A `char` is a Unicode scalar value, which is not always what a reader sees
+/// as a letter: "é" can be written as an "e" followed by a combining accent,
+/// two scalar values but one grapheme cluster.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Letters {
+    Scalars,
+    #[default]
+    Graphemes,
+}
+
+impl Letters {
+    pub fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            Letters::Scalars => text
+                .char_indices()
+                .map(|(i, c)| &text[i..i + c.len_utf8()])
+                .collect(),
+            Letters::Graphemes => text.graphemes(true).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLettersError(String);
+
+impl fmt::Display for ParseLettersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown letters '{}' (expected char or grapheme)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseLettersError {}
+
+/// Why a word cannot be played: it has nothing for the player to guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoLetters;
+
+impl fmt::Display for NoLetters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the word has no letters to guess")
+    }
+}
+
+impl std::error::Error for NoLetters {}
+
+impl FromStr for Letters {
+    type Err = ParseLettersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "char" | "scalar" => Ok(Letters::Scalars),
+            "grapheme" => Ok(Letters::Graphemes),
+            other => Err(ParseLettersError(other.to_string())),
+        }
+    }
+}
+
+/// The words a game of hangman picks from, read from a file with one word
+/// per line. Blank lines, lines starting with `#` and lines without a letter
+/// are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl WordList {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let list: Self = fs::read_to_string(path)?
+            .parse()
+            .expect("parsing never fails");
+        if list.words.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the word list has no words",
+            ));
+        }
+        Ok(list)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// `None` if the list is empty.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&str> {
+        self.words.choose(rng).map(String::as_str)
+    }
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        Self {
+            words: DEFAULT_WORDS.iter().map(|word| word.to_string()).collect(),
+        }
+    }
+}
+
+impl FromStr for WordList {
+    type Err = std::convert::Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| has_letters(line) && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        Ok(Self { words })
+    }
+}
+
+/// What the player is told after a valid guess: how many letters it
+/// uncovered and the word as far as it is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub found: usize,
+    pub mask: String,
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mask = &self.mask;
+        let text = match self.found {
+            0 => i18n::format("hangman.miss", &[("mask", mask)]),
+            found => i18n::plural("hangman.found", found as u64, &[("mask", mask)]),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Hangman: the player guesses the letters of a word one at a time, or the
+/// whole word at once, and loses after too many wrong guesses.
+///
+/// Letters are compared without case, so "з" finds the "З" of
+/// "Здравствуйте", and in Unicode's composed form (NFC), so an "é" typed as
+/// "e" and a combining accent finds a precomposed "é". Anything that is not a
+/// letter, like the space in "crème brûlée", is shown from the start.
+#[derive(Debug, Clone)]
+pub struct HangmanGame {
+    word: String,
+    letters: Letters,
+    // The word split into letters, lowercased, with whether each one has
+    // been found.
+    units: Vec<(String, bool)>,
+    guessed: Vec<String>,
+    attempts: u32,
+    misses: u32,
+    max_misses: Option<u32>,
+}
+
+impl HangmanGame {
+    /// A game of guessing `word`, which needs at least one letter: a word
+    /// with none would be won before the first guess.
+    pub fn new(word: &str, letters: Letters) -> Result<Self, NoLetters> {
+        if !has_letters(word) {
+            return Err(NoLetters);
+        }
+        let word: String = word.nfc().collect();
+        let units = letters
+            .split(&word)
+            .into_iter()
+            .map(|unit| (fold(unit), !has_letters(unit)))
+            .collect();
+        Ok(Self {
+            word,
+            letters,
+            units,
+            guessed: Vec::new(),
+            attempts: 0,
+            misses: 0,
+            max_misses: Some(DEFAULT_MAX_MISSES),
+        })
+    }
+
+    /// `None` lets the player miss as often as they like.
+    pub fn with_max_misses(mut self, max_misses: Option<u32>) -> Self {
+        self.max_misses = max_misses;
+        self
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn letters(&self) -> Letters {
+        self.letters
+    }
+
+    /// How many letters the word has, counted the way this game counts them.
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+
+    /// The letters guessed so far, right or wrong, lowercased.
+    pub fn guessed(&self) -> &[String] {
+        &self.guessed
+    }
+
+    /// The word with every letter not found yet replaced by `_`, and a space
+    /// between letters so that each one can be counted.
+    pub fn mask(&self) -> String {
+        let originals = self.letters.split(&self.word);
+        originals
+            .iter()
+            .zip(&self.units)
+            .map(|(original, (_, found))| if *found { *original } else { "_" })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn progress(&self, found: usize) -> Progress {
+        Progress {
+            found,
+            mask: self.mask(),
+        }
+    }
+}
+
+impl Game for HangmanGame {
+    type Feedback = Progress;
+
+    fn play(&mut self, input: &str) -> Result<Progress, InvalidGuess> {
+        let guess = fold(input.trim());
+        if guess.is_empty() {
+            return Err(InvalidGuess::new(i18n::text("hangman.invalid")));
+        }
+
+        if self.letters.split(&guess).len() > 1 {
+            // A whole word: all or nothing.
+            self.attempts += 1;
+            if guess == fold(&self.word) {
+                let hidden = self.units.iter().filter(|(_, found)| !found).count();
+                for (_, found) in &mut self.units {
+                    *found = true;
+                }
+                return Ok(self.progress(hidden));
+            }
+            self.misses += 1;
+            return Ok(self.progress(0));
+        }
+
+        if !has_letters(&guess) {
+            return Err(InvalidGuess::new(i18n::text("hangman.invalid")));
+        }
+        if self.guessed.contains(&guess) {
+            let repeated = i18n::format("hangman.repeated", &[("guess", &guess)]);
+            return Err(InvalidGuess::new(repeated));
+        }
+        self.guessed.push(guess.clone());
+        self.attempts += 1;
+
+        let mut found = 0;
+        for (unit, revealed) in &mut self.units {
+            if *unit == guess && !*revealed {
+                *revealed = true;
+                found += 1;
+            }
+        }
+        if found == 0 {
+            self.misses += 1;
+        }
+        Ok(self.progress(found))
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Only wrong guesses count towards the limit, so this is how many more
+    /// of them the player can make.
+    fn attempts_left(&self) -> Option<u32> {
+        self.max_misses.map(|max| max.saturating_sub(self.misses))
+    }
+
+    fn is_won(&self) -> bool {
+        self.units.iter().all(|(_, found)| *found)
+    }
+
+    fn is_lost(&self) -> bool {
+        !self.is_won() && self.attempts_left() == Some(0)
+    }
+
+    fn solution(&self) -> String {
+        i18n::format("hangman.solution", &[("word", &self.word)])
+    }
+
+    // The player types a letter, not a number.
+    fn prompt(&self) -> String {
+        i18n::text("hangman.prompt")
+    }
+}
+
+fn has_letters(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+// How the word and the guesses are compared: without case, and composed.
+fn fold(text: &str) -> String {
+    text.to_lowercase().nfc().collect()
+}
+
+/// Plays `game` with guesses read from `input`: letters of a word instead of
+/// a number. Like the number game, it is lost once the attempts run out, but
+/// here only wrong guesses count.
+pub fn play_hangman(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    mut game: HangmanGame,
+) -> io::Result<()> {
+    let args: &[(&str, &dyn fmt::Display)] = &[("mask", &game.mask()), ("length", &game.len())];
+    writeln!(out, "{}", i18n::format("hangman.intro", args))?;
+    play_turns(&mut game, input, out, &mut |_, _, _| Ok(()))
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use super::game::{Game, InvalidGuess};
+use super::{GuessingGame, Outcome};
+use crate::i18n;
+use crate::numbers::is_divisible;
+
+/// How close a wrong guess is to the secret, relative to the size of the
+/// range: within 5% of it is hot, 15% warm, 35% cold, anything further
+/// freezing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proximity {
+    Freezing,
+    Cold,
+    Warm,
+    Hot,
+}
+
+impl Proximity {
+    pub fn of(guess: u32, secret: u32, range: &RangeInclusive<u32>) -> Self {
+        // A range of a single number still has a width of 1, so that a guess
+        // outside of it is not divided by zero.
+        let width = f64::from((range.end() - range.start()).max(1));
+        let distance = f64::from(guess.abs_diff(secret)) / width;
+        if distance <= 0.05 {
+            Proximity::Hot
+        } else if distance <= 0.15 {
+            Proximity::Warm
+        } else if distance <= 0.35 {
+            Proximity::Cold
+        } else {
+            Proximity::Freezing
+        }
+    }
+}
+
+impl fmt::Display for Proximity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let id = match self {
+            Proximity::Freezing => "hint.freezing",
+            Proximity::Cold => "hint.cold",
+            Proximity::Warm => "hint.warm",
+            Proximity::Hot => "hint.hot",
+        };
+        write!(f, "{}", i18n::text(id))
+    }
+}
+
+/// A yes-or-no question about the secret that a player can spend a hint on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Question {
+    Even,
+    /// Always at least 2.
+    DivisibleBy(u32),
+}
+
+impl Question {
+    pub fn answer(self, secret: u32) -> bool {
+        match self {
+            Question::Even => is_divisible(secret, 2),
+            Question::DivisibleBy(divisor) => is_divisible(secret, divisor),
+        }
+    }
+}
+
+impl fmt::Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Question::Even => write!(f, "{}", i18n::text("hint.even")),
+            Question::DivisibleBy(divisor) => {
+                write!(f, "{}", i18n::format("hint.divisible-by", &[("divisor", divisor)]))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseQuestionError(String);
+
+impl fmt::Display for ParseQuestionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot answer '{}', ask 'hint even' or 'hint div <n>' with n at least 2",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseQuestionError {}
+
+// "even", "div 3" or "divisible by 3".
+impl FromStr for Question {
+    type Err = ParseQuestionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let question = s.trim().to_lowercase();
+        if question == "even" {
+            return Ok(Question::Even);
+        }
+        let divisor = question
+            .strip_prefix("divisible by")
+            .or_else(|| question.strip_prefix("div"))
+            .and_then(|divisor| divisor.trim().parse::<u32>().ok());
+        match divisor {
+            Some(divisor) if divisor >= 2 => Ok(Question::DivisibleBy(divisor)),
+            _ => Err(ParseQuestionError(s.trim().to_string())),
+        }
+    }
+}
+
+/// Which help a player gets on top of "too small" and "too big".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HintSettings {
+    /// How many questions the player may ask about the secret.
+    pub budget: u32,
+    /// Say how close every wrong guess is.
+    pub hot_cold: bool,
+}
+
+/// What the player is told about a line of input in a [`HintedGame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clue {
+    /// A guess, with how close it was if the game gives proximity hints.
+    Guess(Outcome, Option<Proximity>),
+    Answer {
+        question: Question,
+        yes: bool,
+        hints_left: u32,
+    },
+}
+
+impl fmt::Display for Clue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Clue::Guess(outcome, None) => write!(f, "{}", outcome),
+            Clue::Guess(outcome, Some(proximity)) => {
+                let args: &[(&str, &dyn fmt::Display)] =
+                    &[("outcome", outcome), ("proximity", proximity)];
+                write!(f, "{}", i18n::format("hint.with-proximity", args))
+            }
+            Clue::Answer {
+                question,
+                yes,
+                hints_left,
+            } => {
+                let id = if *yes { "hint.yes" } else { "hint.no" };
+                let answer = i18n::format(id, &[("question", question)]);
+                let left = i18n::plural("hint.left", u64::from(*hints_left), &[]);
+                write!(f, "{} {}", answer, left)
+            }
+        }
+    }
+}
+
+/// The guessing game with hints. A line starting with `hint` asks a
+/// [`Question`] about the secret instead of guessing; it does not count as an
+/// attempt, but it does cost points on the high-score table (see
+/// [`ScoringModel`](super::ScoringModel)).
+#[derive(Debug, Clone)]
+pub struct HintedGame {
+    game: GuessingGame,
+    settings: HintSettings,
+    hints_used: u32,
+}
+
+impl HintedGame {
+    pub fn new(game: GuessingGame, settings: HintSettings) -> Self {
+        Self {
+            game,
+            settings,
+            hints_used: 0,
+        }
+    }
+
+    pub fn game(&self) -> &GuessingGame {
+        &self.game
+    }
+
+    pub fn hints_used(&self) -> u32 {
+        self.hints_used
+    }
+
+    pub fn hints_left(&self) -> u32 {
+        self.settings.budget - self.hints_used
+    }
+
+    /// Answers `question`, or says why it cannot be asked.
+    pub fn ask(&mut self, question: Question) -> Result<Clue, InvalidGuess> {
+        if self.settings.budget == 0 {
+            return Err(InvalidGuess::new(i18n::text("hint.none")));
+        }
+        if self.hints_left() == 0 {
+            return Err(InvalidGuess::new(i18n::text("hint.used-up")));
+        }
+        self.hints_used += 1;
+        Ok(Clue::Answer {
+            question,
+            yes: question.answer(self.game.secret()),
+            hints_left: self.hints_left(),
+        })
+    }
+}
+
+impl Game for HintedGame {
+    type Feedback = Clue;
+
+    fn play(&mut self, input: &str) -> Result<Clue, InvalidGuess> {
+        if let Some(question) = input.trim().strip_prefix("hint") {
+            let question = question.parse().map_err(|_: ParseQuestionError| {
+                InvalidGuess::new(i18n::format("hint.invalid", &[("question", &question.trim())]))
+            })?;
+            return self.ask(question);
+        }
+
+        let outcome = self.game.play(input)?;
+        let proximity = match outcome {
+            Outcome::TooSmall | Outcome::TooBig if self.settings.hot_cold => {
+                let guess = input.trim().parse().expect("a valid guess is a number");
+                Some(Proximity::of(guess, self.game.secret(), self.game.range()))
+            }
+            _ => None,
+        };
+        Ok(Clue::Guess(outcome, proximity))
+    }
+
+    fn attempts(&self) -> u32 {
+        self.game.attempts()
+    }
+
+    fn attempts_left(&self) -> Option<u32> {
+        self.game.attempts_left()
+    }
+
+    fn is_won(&self) -> bool {
+        self.game.is_won()
+    }
+
+    fn is_lost(&self) -> bool {
+        self.game.is_lost()
+    }
+
+    fn solution(&self) -> String {
+        self.game.solution()
+    }
+}
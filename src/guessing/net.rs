@@ -0,0 +1,631 @@
+//! The guessing game as a race over TCP: a [`Server`] picks a secret per
+//! round and the first player to guess it wins the round. Players and server
+//! exchange one [`Request`] or [`Reply`] per line of text.
+
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{GuessingGame, Outcome, DEFAULT_RANGE};
+use crate::i18n;
+
+/// Where `serve` listens and `connect` connects unless told otherwise.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+// How long a write may block before the player is taken to be gone.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A line sent by a player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// `JOIN <name>`
+    Join(String),
+    /// `GUESS <n>`
+    Guess(u32),
+    /// `QUIT`
+    Quit,
+}
+
+/// A line sent by the server, as an answer or to everyone. Names come last
+/// because they may contain spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// `WELCOME <round> <from> <to> <name>`, the answer to `JOIN`.
+    Welcome {
+        round: u32,
+        range: RangeInclusive<u32>,
+        name: String,
+    },
+    /// `TOO_SMALL <n>`
+    TooSmall(u32),
+    /// `TOO_BIG <n>`
+    TooBig(u32),
+    /// `CORRECT <n>`, the answer to the winning guess.
+    Correct(u32),
+    /// `WINNER <secret> <attempts> <name>`, to everyone.
+    Winner {
+        secret: u32,
+        attempts: u32,
+        name: String,
+    },
+    /// `ROUND <round> <from> <to>`, to everyone when the next round starts.
+    Round {
+        round: u32,
+        range: RangeInclusive<u32>,
+    },
+    /// `ERROR <message>`, the answer to a refused request.
+    Error(String),
+    /// `BYE`, the answer to `QUIT`, and to everyone after the last round.
+    Bye,
+}
+
+/// A line that is not a valid [`Request`] or [`Reply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMessageError(String);
+
+impl fmt::Display for ParseMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a valid message: '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseMessageError {}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Request::Join(name) => write!(f, "JOIN {}", name),
+            Request::Guess(number) => write!(f, "GUESS {}", number),
+            Request::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for Request {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMessageError(line.to_string());
+        let (keyword, rest) = split_keyword(line);
+        match keyword {
+            "JOIN" if !rest.is_empty() => Ok(Request::Join(rest.to_string())),
+            "GUESS" => rest.parse().map(Request::Guess).map_err(|_| invalid()),
+            "QUIT" if rest.is_empty() => Ok(Request::Quit),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reply::Welcome { round, range, name } => write!(
+                f,
+                "WELCOME {} {} {} {}",
+                round,
+                range.start(),
+                range.end(),
+                name
+            ),
+            Reply::TooSmall(number) => write!(f, "TOO_SMALL {}", number),
+            Reply::TooBig(number) => write!(f, "TOO_BIG {}", number),
+            Reply::Correct(number) => write!(f, "CORRECT {}", number),
+            Reply::Winner {
+                secret,
+                attempts,
+                name,
+            } => write!(f, "WINNER {} {} {}", secret, attempts, name),
+            Reply::Round { round, range } => {
+                write!(f, "ROUND {} {} {}", round, range.start(), range.end())
+            }
+            Reply::Error(message) => write!(f, "ERROR {}", message),
+            Reply::Bye => write!(f, "BYE"),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMessageError(line.to_string());
+        let (keyword, rest) = split_keyword(line);
+        // The numbers that follow the keyword, and whatever text is left.
+        let numbers = |count: usize| -> Result<(Vec<u32>, String), ParseMessageError> {
+            let mut fields = rest.splitn(count + 1, ' ');
+            let mut numbers = Vec::with_capacity(count);
+            for _ in 0..count {
+                let field = fields.next().ok_or_else(invalid)?;
+                numbers.push(field.parse().map_err(|_| invalid())?);
+            }
+            Ok((numbers, fields.next().unwrap_or("").to_string()))
+        };
+        let range = |start: u32, end: u32| {
+            if start <= end {
+                Ok(start..=end)
+            } else {
+                Err(invalid())
+            }
+        };
+
+        match keyword {
+            "WELCOME" => match numbers(3)? {
+                (n, name) if !name.is_empty() => Ok(Reply::Welcome {
+                    round: n[0],
+                    range: range(n[1], n[2])?,
+                    name,
+                }),
+                _ => Err(invalid()),
+            },
+            "TOO_SMALL" => rest.parse().map(Reply::TooSmall).map_err(|_| invalid()),
+            "TOO_BIG" => rest.parse().map(Reply::TooBig).map_err(|_| invalid()),
+            "CORRECT" => rest.parse().map(Reply::Correct).map_err(|_| invalid()),
+            "WINNER" => match numbers(2)? {
+                (n, name) if !name.is_empty() => Ok(Reply::Winner {
+                    secret: n[0],
+                    attempts: n[1],
+                    name,
+                }),
+                _ => Err(invalid()),
+            },
+            "ROUND" => match numbers(3)? {
+                (n, rest) if rest.is_empty() => Ok(Reply::Round {
+                    round: n[0],
+                    range: range(n[1], n[2])?,
+                }),
+                _ => Err(invalid()),
+            },
+            "ERROR" => Ok(Reply::Error(rest.to_string())),
+            "BYE" if rest.is_empty() => Ok(Reply::Bye),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+// "GUESS 42\r\n" -> ("GUESS", "42")
+fn split_keyword(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.split_once(' ') {
+        Some((keyword, rest)) => (keyword, rest.trim()),
+        None => (line, ""),
+    }
+}
+
+fn send(stream: &mut TcpStream, message: &impl fmt::Display) -> io::Result<()> {
+    writeln!(stream, "{}", message)
+}
+
+/// How the server runs its rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub range: RangeInclusive<u32>,
+    /// Stop after this many rounds have been won; `None` runs forever.
+    pub rounds: Option<u32>,
+    /// Seed for the secret numbers, to replay the same rounds.
+    pub seed: Option<u64>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            range: DEFAULT_RANGE,
+            rounds: None,
+            seed: None,
+        }
+    }
+}
+
+struct Player {
+    id: u64,
+    name: String,
+    // The queue of the thread that writes to the player's connection.
+    outbox: Sender<Reply>,
+    // The player's own view of the round: same secret, own attempt count.
+    game: GuessingGame,
+}
+
+// Everything the connection threads share.
+struct State {
+    config: ServerConfig,
+    rng: StdRng,
+    round: u32,
+    secret: u32,
+    players: Vec<Player>,
+    // Every open connection, joined or not, to close them when the game ends.
+    connections: Vec<(u64, TcpStream)>,
+    finished: bool,
+}
+
+impl State {
+    fn player(&mut self, id: u64) -> Option<&mut Player> {
+        self.players.iter_mut().find(|player| player.id == id)
+    }
+
+    // Queues a reply for every player. A player whose connection is gone is
+    // dropped when its own thread notices, so errors are ignored here.
+    fn announce(&self, reply: &Reply) {
+        for player in &self.players {
+            let _ = player.outbox.send(reply.clone());
+        }
+    }
+
+    fn start_round(&mut self) {
+        self.round += 1;
+        self.secret = self.rng.gen_range(self.config.range.clone());
+        let (secret, range) = (self.secret, self.config.range.clone());
+        for player in &mut self.players {
+            player.game = GuessingGame::with_secret(secret, range.clone());
+        }
+    }
+}
+
+/// A guessing game server. Every connection is handled on its own thread.
+pub struct Server {
+    listener: TcpListener,
+    state: Arc<Mutex<State>>,
+}
+
+impl Server {
+    /// Listens on `addr`. Use port 0 to let the system pick a free port, and
+    /// [`Server::local_addr`] to find out which one it picked.
+    pub fn bind(addr: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut state = State {
+            config,
+            rng,
+            round: 0,
+            secret: 0,
+            players: Vec::new(),
+            connections: Vec::new(),
+            finished: false,
+        };
+        state.start_round();
+        Ok(Self {
+            listener,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts players until the last round is over; forever if there is no
+    /// last round.
+    pub fn run(self) -> io::Result<()> {
+        let addr = self.local_addr()?;
+        let mut handlers = Vec::new();
+        for (id, stream) in (0..).zip(self.listener.incoming()) {
+            if self.state.lock().unwrap().finished {
+                break;
+            }
+            let stream = match stream {
+                Ok(stream) => stream,
+                // The client gave up before it was accepted.
+                Err(_) => continue,
+            };
+            self.state
+                .lock()
+                .unwrap()
+                .connections
+                .push((id, stream.try_clone()?));
+            stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+            let (outbox, replies) = mpsc::channel();
+            let writer = {
+                let stream = stream.try_clone()?;
+                thread::spawn(move || write_replies(stream, replies))
+            };
+            let state = Arc::clone(&self.state);
+            handlers.push(thread::spawn(move || {
+                // A player that disconnects abruptly only ends its own thread.
+                let _ = handle_player(id, stream, outbox, &state, addr);
+                {
+                    let mut state = state.lock().unwrap();
+                    state.players.retain(|player| player.id != id);
+                    state
+                        .connections
+                        .retain(|(connection, _)| *connection != id);
+                }
+                // Every sender is gone now, so the writer stops once it has
+                // written what is left in the queue.
+                let _ = writer.join();
+            }));
+            // Only the threads of players still connected need joining.
+            handlers.retain(|handler| !handler.is_finished());
+        }
+        for handler in handlers {
+            let _ = handler.join();
+        }
+        Ok(())
+    }
+}
+
+fn handle_player(
+    id: u64,
+    stream: TcpStream,
+    outbox: Sender<Reply>,
+    state: &Mutex<State>,
+    server_addr: SocketAddr,
+) -> io::Result<()> {
+    // Replies are only queued here; the connection's writer thread sends
+    // them, so a player that stops reading never holds up the others. If the
+    // writer gave up, it shut the connection down, which ends this loop.
+    let answer = |reply: Reply| {
+        let _ = outbox.send(reply);
+    };
+
+    for line in BufReader::new(stream).lines() {
+        let request = match line?.parse::<Request>() {
+            Ok(request) => request,
+            Err(error) => {
+                answer(Reply::Error(error.to_string()));
+                continue;
+            }
+        };
+
+        // Everything is queued while holding the lock, so that every player
+        // sees the announcements in the same order.
+        let mut state = state.lock().unwrap();
+        if state.finished {
+            // The game ended while this request was on its way, and the
+            // player has already been told.
+            break;
+        }
+        match request {
+            Request::Join(name) => {
+                if state.player(id).is_some() {
+                    answer(Reply::Error("already joined".to_string()));
+                } else if state.players.iter().any(|player| player.name == name) {
+                    answer(Reply::Error(format!("the name '{}' is taken", name)));
+                } else {
+                    let range = state.config.range.clone();
+                    let game = GuessingGame::with_secret(state.secret, range.clone());
+                    state.players.push(Player {
+                        id,
+                        name: name.clone(),
+                        outbox: outbox.clone(),
+                        game,
+                    });
+                    let round = state.round;
+                    answer(Reply::Welcome { round, range, name });
+                }
+            }
+            Request::Guess(number) => {
+                let player = match state.player(id) {
+                    Some(player) => player,
+                    None => {
+                        answer(Reply::Error("JOIN first".to_string()));
+                        continue;
+                    }
+                };
+                match player.game.guess_number(number) {
+                    Outcome::TooSmall => answer(Reply::TooSmall(number)),
+                    Outcome::TooBig => answer(Reply::TooBig(number)),
+                    Outcome::Win => {
+                        let winner = Reply::Winner {
+                            secret: number,
+                            attempts: player.game.attempts(),
+                            name: player.name.clone(),
+                        };
+                        answer(Reply::Correct(number));
+                        state.announce(&winner);
+                        finish_round(&mut state, server_addr);
+                    }
+                    // There is no attempt limit and the number is parsed.
+                    Outcome::Lost | Outcome::Invalid => unreachable!(),
+                }
+            }
+            Request::Quit => {
+                answer(Reply::Bye);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Sends a connection's replies in order until every sender is gone. A player
+// that stops reading only blocks this thread, until the write times out.
+fn write_replies(mut stream: TcpStream, replies: Receiver<Reply>) {
+    for reply in replies {
+        if send(&mut stream, &reply).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+            break;
+        }
+    }
+}
+
+// Starts the next round, or ends the game after the last one.
+fn finish_round(state: &mut State, server_addr: SocketAddr) {
+    if Some(state.round) != state.config.rounds {
+        state.start_round();
+        let round = state.round;
+        let range = state.config.range.clone();
+        state.announce(&Reply::Round { round, range });
+        return;
+    }
+
+    state.finished = true;
+    state.announce(&Reply::Bye);
+    for (_, stream) in &state.connections {
+        // Ends the reading loop of every connection's thread; the writers
+        // still send what is queued, `Bye` included, before closing.
+        let _ = stream.shutdown(Shutdown::Read);
+    }
+    // `Server::run` is blocked waiting for the next player; be that player so
+    // it gets to see that the game is over.
+    let _ = TcpStream::connect(server_addr);
+}
+
+/// A connection to a [`Server`].
+///
+/// Replies are read on a background thread, so announcements are not lost
+/// while the player is still thinking about the next guess.
+pub struct Client {
+    stream: TcpStream,
+    replies: Receiver<Reply>,
+}
+
+impl Client {
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let (sender, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let reply = match line {
+                    Ok(line) => line
+                        .parse()
+                        .unwrap_or_else(|error: ParseMessageError| Reply::Error(error.to_string())),
+                    Err(_) => break,
+                };
+                if sender.send(reply).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { stream, replies })
+    }
+
+    pub fn send(&mut self, request: &Request) -> io::Result<()> {
+        send(&mut self.stream, request)
+    }
+
+    /// Waits for the next reply; `None` once the server has closed the
+    /// connection and every reply has been received.
+    pub fn receive(&mut self) -> Option<Reply> {
+        self.replies.recv().ok()
+    }
+
+    /// The next reply if one has already arrived.
+    pub fn try_receive(&mut self) -> Option<Reply> {
+        self.replies.try_recv().ok()
+    }
+
+    /// Joins the game as `name` and returns the server's answer, which is
+    /// [`Reply::Welcome`] unless the name was refused.
+    pub fn join(&mut self, name: &str) -> io::Result<Reply> {
+        self.send(&Request::Join(name.to_string()))?;
+        self.receive().ok_or_else(closed)
+    }
+}
+
+fn closed() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        "the server closed the connection",
+    )
+}
+
+/// Plays on `client` with guesses read from `input`, one per line, until
+/// `input` ends or the server ends the game.
+pub fn play_online(
+    client: &mut Client,
+    name: &str,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match client.join(name)? {
+        reply @ Reply::Welcome { .. } => describe(&reply, out)?,
+        Reply::Error(message) => {
+            return Err(io::Error::other(message));
+        }
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                other.to_string(),
+            ))
+        }
+    }
+
+    loop {
+        // Show what happened while the player was typing.
+        while let Some(reply) = client.try_receive() {
+            describe(&reply, out)?;
+            if reply == Reply::Bye {
+                return Ok(());
+            }
+        }
+
+        writeln!(out, "{}", i18n::text("guess.prompt"))?;
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            // The server may already be gone, so failing to say goodbye is
+            // fine; show whatever it still sent.
+            let _ = client.send(&Request::Quit);
+            while let Some(reply) = client.receive() {
+                describe(&reply, out)?;
+            }
+            return Ok(());
+        }
+        let guess: u32 = match guess.trim().parse() {
+            Ok(number) => number,
+            Err(_) => {
+                writeln!(out, "{}", Outcome::Invalid)?;
+                continue;
+            }
+        };
+        client.send(&Request::Guess(guess))?;
+
+        // Read up to the answer to this guess. A winning guess is followed by
+        // the announcements it causes, so wait for those too.
+        let mut won = false;
+        loop {
+            let reply = client.receive().ok_or_else(closed)?;
+            describe(&reply, out)?;
+            match reply {
+                Reply::Bye => return Ok(()),
+                Reply::Correct(_) => won = true,
+                Reply::Round { .. } if won => break,
+                Reply::TooSmall(_) | Reply::TooBig(_) | Reply::Error(_) => break,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn describe(reply: &Reply, out: &mut dyn Write) -> io::Result<()> {
+    // In the language of the player, whatever the server's is.
+    let text = match reply {
+        Reply::Welcome { round, range, name } => i18n::format(
+            "online.joined",
+            &[
+                ("round", round),
+                ("name", name),
+                ("start", range.start()),
+                ("end", range.end()),
+            ],
+        ),
+        Reply::TooSmall(_) => Outcome::TooSmall.to_string(),
+        Reply::TooBig(_) => Outcome::TooBig.to_string(),
+        Reply::Correct(_) => Outcome::Win.to_string(),
+        Reply::Winner {
+            secret,
+            attempts,
+            name,
+        } => i18n::format(
+            "online.winner",
+            &[("name", name), ("attempts", attempts), ("secret", secret)],
+        ),
+        Reply::Round { round, range } => i18n::format(
+            "online.round",
+            &[("round", round), ("start", range.start()), ("end", range.end())],
+        ),
+        Reply::Error(message) => i18n::format("online.error", &[("message", message)]),
+        Reply::Bye => i18n::text("online.over"),
+    };
+    writeln!(out, "{}", text)
+}
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+use super::{Difficulty, GuessingGame, Outcome};
+
+/// Where sessions are recorded unless told otherwise.
+pub const DEFAULT_REPLAY_DIR: &str = "hello-rust-replays";
+
+// First line of every replay log; the number changes with the format.
+const HEADER: &str = "hello-rust-replay 1";
+
+/// One line of input and what the game answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedGuess {
+    /// Time since the session started.
+    pub elapsed: Duration,
+    /// The line as typed, without surrounding whitespace.
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+/// Everything needed to play a session of the guessing game again: the seed
+/// the secret was picked with, the rules, and every line the player typed.
+///
+/// The log is plain text, one field per line and one line per guess:
+///
+/// ```text
+/// hello-rust-replay 1
+/// seed 13787212812718412097
+/// range 0 100
+/// max-attempts 8
+/// started 1760000000
+/// guess 2310 too-big 50
+/// guess 3480 invalid twenty
+/// guess 5012 win 20
+/// ```
+///
+/// `max-attempts` is `none` for unlimited games, `started` is in seconds
+/// since the Unix epoch and the number after `guess` is in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub seed: u64,
+    pub range: RangeInclusive<u32>,
+    pub max_attempts: Option<u32>,
+    /// When the session started, in seconds since the Unix epoch.
+    pub started: u64,
+    pub guesses: Vec<RecordedGuess>,
+}
+
+impl Session {
+    /// A session starting now, before any guess.
+    pub fn new(seed: u64, difficulty: &Difficulty) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        Self {
+            seed,
+            range: difficulty.range(),
+            max_attempts: difficulty.max_attempts(),
+            started,
+            guesses: Vec::new(),
+        }
+    }
+
+    /// The game as it was at the start of the session. The same seed always
+    /// picks the same secret.
+    pub fn game(&self) -> GuessingGame {
+        // Named rather than `StdRng`, whose algorithm may change with rand
+        // versions and leave old logs with a different secret. ChaCha12 is
+        // what `StdRng` was when the format was introduced.
+        let mut rng = ChaCha12Rng::seed_from_u64(self.seed);
+        GuessingGame::with_range(&mut rng, self.range.clone()).with_max_attempts(self.max_attempts)
+    }
+
+    /// Plays the recorded input again and checks that the game still answers
+    /// every line the way it did when the session was recorded.
+    pub fn replay(&self) -> Result<GuessingGame, Mismatch> {
+        let mut game = self.game();
+        for (index, recorded) in self.guesses.iter().enumerate() {
+            let outcome = game.guess(&recorded.input);
+            if outcome != recorded.outcome {
+                return Err(Mismatch {
+                    line: index + 1,
+                    input: recorded.input.clone(),
+                    recorded: recorded.outcome,
+                    replayed: outcome,
+                });
+            }
+        }
+        Ok(game)
+    }
+
+    // Everything before the guesses.
+    fn header(&self) -> String {
+        let max_attempts = match self.max_attempts {
+            Some(max) => max.to_string(),
+            None => String::from("none"),
+        };
+        format!(
+            "{}\nseed {}\nrange {} {}\nmax-attempts {}\nstarted {}\n",
+            HEADER,
+            self.seed,
+            self.range.start(),
+            self.range.end(),
+            max_attempts,
+            self.started
+        )
+    }
+
+    /// Reads a log written by a [`Recorder`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let text = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        text.parse()
+    }
+}
+
+impl RecordedGuess {
+    fn to_line(&self) -> String {
+        format!(
+            "guess {} {} {}",
+            self.elapsed.as_millis(),
+            outcome_name(self.outcome),
+            self.input
+        )
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.header())?;
+        for guess in &self.guesses {
+            writeln!(f, "{}", guess.to_line())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Session {
+    type Err = ReplayError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line));
+        let mut field = |name: &str| -> Result<(usize, String), ReplayError> {
+            let (number, line) = lines.next().ok_or_else(|| ReplayError::Parse {
+                line: 0,
+                reason: format!("the log ends before '{}'", name),
+            })?;
+            match line.strip_prefix(name) {
+                Some(value) => Ok((number, value.trim().to_string())),
+                None => Err(ReplayError::Parse {
+                    line: number,
+                    reason: format!("expected '{}'", name),
+                }),
+            }
+        };
+        let invalid = |line: usize, what: &str| ReplayError::Parse {
+            line,
+            reason: format!("invalid {}", what),
+        };
+
+        let (number, version) = field("hello-rust-replay")?;
+        if version != "1" {
+            return Err(ReplayError::Parse {
+                line: number,
+                reason: format!("unknown replay version '{}'", version),
+            });
+        }
+        let (number, seed) = field("seed")?;
+        let seed = seed.parse().map_err(|_| invalid(number, "seed"))?;
+        let (number, range) = field("range")?;
+        let range = match range.split_once(' ') {
+            Some((start, end)) => match (start.parse::<u32>(), end.parse::<u32>()) {
+                (Ok(start), Ok(end)) if start <= end => start..=end,
+                _ => return Err(invalid(number, "range")),
+            },
+            None => return Err(invalid(number, "range")),
+        };
+        let (number, max_attempts) = field("max-attempts")?;
+        let max_attempts = match max_attempts.as_str() {
+            "none" => None,
+            max => Some(max.parse().map_err(|_| invalid(number, "attempt limit"))?),
+        };
+        let (number, started) = field("started")?;
+        let started = started.parse().map_err(|_| invalid(number, "start time"))?;
+
+        let mut guesses = Vec::new();
+        for (number, line) in lines {
+            // The input comes last and may itself contain spaces.
+            let mut fields = line.splitn(4, ' ');
+            let guess = match (fields.next(), fields.next(), fields.next()) {
+                (Some("guess"), Some(elapsed), Some(outcome)) => RecordedGuess {
+                    elapsed: Duration::from_millis(
+                        elapsed.parse().map_err(|_| invalid(number, "time"))?,
+                    ),
+                    outcome: parse_outcome(outcome).ok_or_else(|| invalid(number, "feedback"))?,
+                    input: fields.next().unwrap_or("").to_string(),
+                },
+                _ => return Err(invalid(number, "guess")),
+            };
+            guesses.push(guess);
+        }
+
+        Ok(Self {
+            seed,
+            range,
+            max_attempts,
+            started,
+            guesses,
+        })
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => "too-small",
+        Outcome::TooBig => "too-big",
+        Outcome::Win => "win",
+        Outcome::Lost => "lost",
+        Outcome::Invalid => "invalid",
+    }
+}
+
+fn parse_outcome(name: &str) -> Option<Outcome> {
+    match name {
+        "too-small" => Some(Outcome::TooSmall),
+        "too-big" => Some(Outcome::TooBig),
+        "win" => Some(Outcome::Win),
+        "lost" => Some(Outcome::Lost),
+        "invalid" => Some(Outcome::Invalid),
+        _ => None,
+    }
+}
+
+/// Writes a session to its log as it is played, one line per guess, so that
+/// the log is complete even if the program stops halfway through the game.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    file: File,
+    session: Session,
+}
+
+impl Recorder {
+    /// Starts a log in `dir`, which is created if needed. Every session gets
+    /// its own file, named after its start time and seed.
+    pub fn create(dir: impl AsRef<Path>, session: Session) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}-{}.replay", session.started, session.seed));
+        let mut file = File::create(&path)?;
+        write!(file, "{}", session)?;
+        Ok(Self {
+            path,
+            file,
+            session,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn record(&mut self, elapsed: Duration, input: &str, outcome: Outcome) -> io::Result<()> {
+        let guess = RecordedGuess {
+            elapsed,
+            input: input.trim().to_string(),
+            outcome,
+        };
+        writeln!(self.file, "{}", guess.to_line())?;
+        self.file.flush()?;
+        self.session.guesses.push(guess);
+        Ok(())
+    }
+}
+
+/// A recorded line the game now answers differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Which guess, counting from 1.
+    pub line: usize,
+    pub input: String,
+    pub recorded: Outcome,
+    pub replayed: Outcome,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "guess {} ('{}') was answered {} when recorded, but {} now",
+            self.line,
+            self.input,
+            outcome_name(self.recorded),
+            outcome_name(self.replayed)
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    /// `line` counts from 1; 0 means the log ended too early.
+    Parse {
+        line: usize,
+        reason: String,
+    },
+    Mismatch(Mismatch),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "{}", error),
+            ReplayError::Parse { line: 0, reason } => write!(f, "{}", reason),
+            ReplayError::Parse { line, reason } => write!(f, "line {}: {}", line, reason),
+            ReplayError::Mismatch(mismatch) => write!(f, "{}", mismatch),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<Mismatch> for ReplayError {
+    fn from(mismatch: Mismatch) -> Self {
+        ReplayError::Mismatch(mismatch)
+    }
+}
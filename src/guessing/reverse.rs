@@ -0,0 +1,270 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::i18n;
+
+/// What the player says about the program's guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// The player's number is higher than the guess.
+    Higher,
+    /// The player's number is lower than the guess.
+    Lower,
+    Correct,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAnswerError(String);
+
+impl fmt::Display for ParseAnswerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", i18n::format("reverse.invalid", &[("answer", &self.0)]))
+    }
+}
+
+impl std::error::Error for ParseAnswerError {}
+
+// "higher", "lower" and "correct", their first letters, or a symbol.
+impl FromStr for Answer {
+    type Err = ParseAnswerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "higher" | "h" | "+" | ">" => Ok(Answer::Higher),
+            "lower" | "l" | "-" | "<" => Ok(Answer::Lower),
+            "correct" | "c" | "=" | "yes" | "y" => Ok(Answer::Correct),
+            other => Err(ParseAnswerError(other.to_string())),
+        }
+    }
+}
+
+/// Why the player's number can be no lower, or no higher, than it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The number was picked from the range, so it is at least its start.
+    RangeStart(u32),
+    /// The number was picked from the range, so it is at most its end.
+    RangeEnd(u32),
+    /// The answer to the program's `turn`th guess (counting from 1).
+    Answer {
+        turn: u32,
+        guess: u32,
+        answer: Answer,
+    },
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Bound::RangeStart(start) => i18n::format("reverse.range-start", &[("start", start)]),
+            Bound::RangeEnd(end) => i18n::format("reverse.range-end", &[("end", end)]),
+            Bound::Answer {
+                turn,
+                guess,
+                answer,
+            } => {
+                let id = match answer {
+                    Answer::Higher => "reverse.higher",
+                    Answer::Lower => "reverse.lower",
+                    Answer::Correct => "reverse.exactly",
+                };
+                i18n::format(id, &[("guess", guess), ("turn", turn)])
+            }
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Two things the player said that no number satisfies at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    /// Where the lowest possible number came from.
+    pub lower: Bound,
+    /// Where the highest possible number came from; it is below `lower`.
+    pub upper: Bound,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let args: &[(&str, &dyn fmt::Display)] = &[("lower", &self.lower), ("upper", &self.upper)];
+        write!(f, "{}", i18n::format("reverse.contradiction", args))
+    }
+}
+
+impl std::error::Error for Contradiction {}
+
+/// The guessing game the other way round: the player thinks of a number and
+/// the program guesses it, narrowing down the numbers that are still possible
+/// with every answer.
+///
+/// ```
+/// use hello_rust::guessing::{Answer, ReverseGame};
+///
+/// let mut game = ReverseGame::new(0..=100);
+/// assert_eq!(game.next_guess(), Some(50));
+/// game.answer(50, Answer::Lower).unwrap();
+/// game.answer(24, Answer::Higher).unwrap();
+/// assert_eq!(game.candidates(), 25..=49);
+///
+/// let contradiction = game.answer(20, Answer::Correct).unwrap_err();
+/// assert_eq!(
+///     contradiction.to_string(),
+///     "your number cannot be both higher than 24 (your answer to guess 2) \
+///      and exactly 20 (your answer to guess 3)",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReverseGame {
+    range: RangeInclusive<u32>,
+    low: (u32, Bound),
+    high: (u32, Bound),
+    guesses: u32,
+    found: Option<u32>,
+}
+
+impl ReverseGame {
+    pub fn new(range: RangeInclusive<u32>) -> Self {
+        let (start, end) = (*range.start(), *range.end());
+        Self {
+            range,
+            low: (start, Bound::RangeStart(start)),
+            high: (end, Bound::RangeEnd(end)),
+            guesses: 0,
+            found: None,
+        }
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    /// The numbers the player's number can still be.
+    pub fn candidates(&self) -> RangeInclusive<u32> {
+        self.low.0..=self.high.0
+    }
+
+    /// The middle of the candidates, or `None` once the number is found.
+    pub fn next_guess(&self) -> Option<u32> {
+        match self.found {
+            Some(_) => None,
+            None => Some(self.low.0 + (self.high.0 - self.low.0) / 2),
+        }
+    }
+
+    /// Number of guesses answered so far.
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    /// The player's number, once they have answered correct.
+    pub fn found(&self) -> Option<u32> {
+        self.found
+    }
+
+    /// Takes the player's answer to `guess`. An answer that leaves no number
+    /// possible is not taken, and the error names the two answers (or the
+    /// answer and the end of the range) that disagree.
+    pub fn answer(&mut self, guess: u32, answer: Answer) -> Result<(), Contradiction> {
+        let bound = Bound::Answer {
+            turn: self.guesses + 1,
+            guess,
+            answer,
+        };
+        // The lowest and highest number this answer allows on its own. "Higher
+        // than u32::MAX" allows nothing, which is past any range end.
+        let (low, high) = match answer {
+            Answer::Higher => match guess.checked_add(1) {
+                Some(low) => (Some(low), None),
+                None => {
+                    return Err(Contradiction {
+                        lower: bound,
+                        upper: self.high.1,
+                    })
+                }
+            },
+            Answer::Lower => match guess.checked_sub(1) {
+                Some(high) => (None, Some(high)),
+                None => {
+                    return Err(Contradiction {
+                        lower: self.low.1,
+                        upper: bound,
+                    })
+                }
+            },
+            Answer::Correct => (Some(guess), Some(guess)),
+        };
+
+        let mut next_low = self.low;
+        let mut next_high = self.high;
+        if let Some(low) = low.filter(|low| *low > self.low.0) {
+            next_low = (low, bound);
+        }
+        if let Some(high) = high.filter(|high| *high < self.high.0) {
+            next_high = (high, bound);
+        }
+        // "Correct" for a number that is still possible tightens both sides;
+        // for one that is not, it only tightens one and the check below fails.
+        if answer == Answer::Correct && next_low.0 <= next_high.0 {
+            next_low = (guess, bound);
+            next_high = (guess, bound);
+        }
+        if next_low.0 > next_high.0 {
+            return Err(Contradiction {
+                lower: next_low.1,
+                upper: next_high.1,
+            });
+        }
+
+        self.low = next_low;
+        self.high = next_high;
+        self.guesses += 1;
+        if answer == Answer::Correct {
+            self.found = Some(guess);
+        }
+        Ok(())
+    }
+}
+
+/// The other way round: the player thinks of a number, answers on `input`,
+/// and the program guesses it.
+pub fn play_reverse(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    range: RangeInclusive<u32>,
+) -> io::Result<()> {
+    let mut game = ReverseGame::new(range);
+
+    let range = game.range();
+    let intro = i18n::format("reverse.intro", &[("start", range.start()), ("end", range.end())]);
+    writeln!(out, "{intro}")?;
+
+    while let Some(guess) = game.next_guess() {
+        writeln!(out, "{}", i18n::format("reverse.question", &[("guess", &guess)]))?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(()); // no more input
+        }
+
+        let answer: Answer = match answer.parse() {
+            Ok(answer) => answer,
+            Err(error) => {
+                writeln!(out, "{error}")?;
+                continue;
+            }
+        };
+        // An answer that contradicts an earlier one ends the game: there is no
+        // number left to guess.
+        if let Err(contradiction) = game.answer(guess, answer) {
+            let args: &[(&str, &dyn fmt::Display)] = &[("contradiction", &contradiction)];
+            writeln!(out, "{}", i18n::format("reverse.wrong", args))?;
+            return Ok(());
+        }
+    }
+
+    let guesses = u64::from(game.guesses());
+    writeln!(out, "{}", i18n::plural("reverse.found", guesses, &[]))?;
+    Ok(())
+}
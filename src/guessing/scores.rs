@@ -0,0 +1,366 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::duration::Span;
+use crate::i18n;
+
+/// Where the high-score table is kept unless told otherwise.
+pub const DEFAULT_SCORE_FILE: &str = "hello-rust-scores.tsv";
+
+/// How many scores are kept for every range.
+pub const MAX_SCORES_PER_RANGE: usize = 10;
+
+/// One won game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub name: String,
+    pub attempts: u32,
+    pub elapsed: Duration,
+    pub range: RangeInclusive<u32>,
+    /// When the game was won, in seconds since the Unix epoch.
+    pub date: u64,
+    /// Questions asked about the secret (see `HintedGame`).
+    pub hints: u32,
+}
+
+impl Score {
+    /// A score dated now.
+    pub fn new(name: &str, attempts: u32, elapsed: Duration, range: RangeInclusive<u32>) -> Self {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        Self {
+            name: clean_name(name),
+            attempts,
+            elapsed,
+            range,
+            date,
+            hints: 0,
+        }
+    }
+
+    pub fn with_hints(mut self, hints: u32) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    /// How long before `now` (in seconds since the Unix epoch) the game was
+    /// won; zero for a score dated in the future.
+    pub fn age(&self, now: u64) -> Span {
+        Span::from_secs(now.saturating_sub(self.date))
+    }
+
+    // name, attempts, elapsed milliseconds, range start, range end, date, hints
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.name,
+            self.attempts,
+            self.elapsed.as_millis(),
+            self.range.start(),
+            self.range.end(),
+            self.date,
+            self.hints
+        )
+    }
+
+    // Lines written before hints were added have six fields.
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 6 && fields.len() != 7 {
+            return None;
+        }
+        let start: u32 = fields[3].parse().ok()?;
+        let end: u32 = fields[4].parse().ok()?;
+        if start > end {
+            return None;
+        }
+        Some(Self {
+            name: clean_name(fields[0]),
+            attempts: fields[1].parse().ok()?,
+            elapsed: Duration::from_millis(fields[2].parse().ok()?),
+            range: start..=end,
+            date: fields[5].parse().ok()?,
+            hints: match fields.get(6) {
+                Some(hints) => hints.parse().ok()?,
+                None => 0,
+            },
+        })
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<16} {:>3} {} {:>2} {}  {:>7.1}s  {}",
+            self.name,
+            self.attempts,
+            i18n::text("scores.attempts"),
+            self.hints,
+            i18n::text("scores.hints"),
+            self.elapsed.as_secs_f64(),
+            format_date(self.date)
+        )
+    }
+}
+
+// Tabs and newlines would break the file format.
+fn clean_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if name.is_empty() {
+        String::from("anonymous")
+    } else {
+        name
+    }
+}
+
+/// Decides how won games rank: the more points, the better. On a tie, the
+/// faster game ranks first.
+///
+/// Only what was played is stored, not the points, so the same table can be
+/// ranked by any model.
+pub trait ScoringModel: fmt::Debug {
+    fn name(&self) -> &str;
+
+    fn points(&self, score: &Score) -> i64;
+}
+
+/// A fixed number of points to start with, minus a cost for every guess and
+/// every hint. This is the default: without hints, fewer guesses always rank
+/// first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessCost {
+    pub start: i64,
+    pub per_attempt: i64,
+    pub per_hint: i64,
+}
+
+impl Default for GuessCost {
+    fn default() -> Self {
+        Self {
+            start: 1000,
+            per_attempt: 100,
+            per_hint: 50,
+        }
+    }
+}
+
+impl ScoringModel for GuessCost {
+    fn name(&self) -> &str {
+        "guesses"
+    }
+
+    fn points(&self, score: &Score) -> i64 {
+        self.start
+            - self.per_attempt * i64::from(score.attempts)
+            - self.per_hint * i64::from(score.hints)
+    }
+}
+
+/// Like [`GuessCost`], but starts with 100 points for every bit of the
+/// secret: a game on 0..=1000 is worth more than one on 0..=50. A guess and a
+/// hint each cost one bit, as a perfect yes-or-no answer would tell the
+/// player that much.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RangeBits;
+
+impl ScoringModel for RangeBits {
+    fn name(&self) -> &str {
+        "range"
+    }
+
+    fn points(&self, score: &Score) -> i64 {
+        let size = u64::from(score.range.end() - score.range.start()) + 1;
+        let bits = (size as f64).log2();
+        let guesses = i64::from(score.attempts) + i64::from(score.hints);
+        (100.0 * bits).round() as i64 - 100 * guesses
+    }
+}
+
+/// Names accepted by [`scoring_by_name`], the default first.
+pub const SCORING_MODELS: [&str; 2] = ["guesses", "range"];
+
+pub fn scoring_by_name(name: &str) -> Option<Box<dyn ScoringModel>> {
+    match name {
+        "guesses" => Some(Box::new(GuessCost::default())),
+        "range" => Some(Box::new(RangeBits)),
+        _ => None,
+    }
+}
+
+/// The high-score table, stored as one tab-separated line per score.
+#[derive(Debug)]
+pub struct ScoreTable {
+    path: PathBuf,
+    scores: Vec<Score>,
+    skipped_lines: usize,
+    scoring: Box<dyn ScoringModel>,
+}
+
+impl ScoreTable {
+    /// Loads the table from `path`. A missing file is created empty, and lines
+    /// that cannot be read back as a score are skipped rather than failing
+    /// the whole table. Scores are ranked with [`GuessCost`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load_with(path, Box::new(GuessCost::default()))
+    }
+
+    /// Like [`ScoreTable::load`], ranking the scores with `scoring`. Saving
+    /// keeps the best [`MAX_SCORES_PER_RANGE`] by [`GuessCost`] whatever the
+    /// model, so looking at the table another way loses no scores.
+    pub fn load_with(path: impl AsRef<Path>, scoring: Box<dyn ScoringModel>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        // Same approach as part10::recoverable_errors: a file that does not
+        // exist yet is created (and so has nothing to read), any other error
+        // is passed on. Raw bytes are read so that a file that is not valid
+        // UTF-8 loses only its broken lines.
+        let mut bytes = Vec::new();
+        match File::open(&path) {
+            Ok(mut file) => {
+                file.read_to_end(&mut bytes)?;
+            }
+            Err(error) => match error.kind() {
+                io::ErrorKind::NotFound => {
+                    File::create(&path)?;
+                }
+                _ => return Err(error),
+            },
+        }
+        let text = String::from_utf8_lossy(&bytes);
+
+        let mut scores = Vec::new();
+        let mut skipped_lines = 0;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            match Score::from_line(line) {
+                Some(score) => scores.push(score),
+                None => skipped_lines += 1,
+            }
+        }
+
+        let mut table = Self {
+            path,
+            scores,
+            skipped_lines,
+            scoring,
+        };
+        table.rank();
+        Ok(table)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn scoring(&self) -> &dyn ScoringModel {
+        self.scoring.as_ref()
+    }
+
+    /// Number of lines in the file that were not valid scores.
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    /// Adds a score and returns its 1-based rank within its range, or `None`
+    /// when it did not make the top [`MAX_SCORES_PER_RANGE`].
+    pub fn add(&mut self, score: Score) -> Option<usize> {
+        let range = score.range.clone();
+        self.scores.push(score.clone());
+        self.rank();
+        self.top(&range, MAX_SCORES_PER_RANGE)
+            .iter()
+            .position(|kept| **kept == score)
+            .map(|index| index + 1)
+    }
+
+    /// The best `n` scores for games played on `range`.
+    pub fn top(&self, range: &RangeInclusive<u32>, n: usize) -> Vec<&Score> {
+        self.scores
+            .iter()
+            .filter(|score| score.range == *range)
+            .take(n)
+            .collect()
+    }
+
+    /// Every range that has at least one score, smallest first.
+    pub fn ranges(&self) -> Vec<RangeInclusive<u32>> {
+        let mut ranges: Vec<RangeInclusive<u32>> = Vec::new();
+        for score in &self.scores {
+            if !ranges.contains(&score.range) {
+                ranges.push(score.range.clone());
+            }
+        }
+        ranges.sort_by_key(|range| (*range.end() - *range.start(), *range.start()));
+        ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Writes the table back to its file, dropping any lines that were skipped
+    /// and all but the best [`MAX_SCORES_PER_RANGE`] scores of each range.
+    pub fn save(&self) -> io::Result<()> {
+        let mut scores = self.scores.clone();
+        sort_by_points(&mut scores, &GuessCost::default());
+        let mut kept: Vec<&Score> = Vec::new();
+        for score in &scores {
+            let in_range = kept.iter().filter(|k| k.range == score.range).count();
+            if in_range < MAX_SCORES_PER_RANGE {
+                kept.push(score);
+            }
+        }
+        let mut file = File::create(&self.path)?;
+        for score in kept {
+            writeln!(file, "{}", score.to_line())?;
+        }
+        Ok(())
+    }
+
+    /// Removes every score, or only those for `range`, and saves the table.
+    pub fn reset(&mut self, range: Option<&RangeInclusive<u32>>) -> io::Result<()> {
+        match range {
+            Some(range) => self.scores.retain(|score| score.range != *range),
+            None => self.scores.clear(),
+        }
+        self.save()
+    }
+
+    // Sorts by rank within each range. Every score is kept until saving.
+    fn rank(&mut self) {
+        sort_by_points(&mut self.scores, self.scoring.as_ref());
+    }
+}
+
+// Most points first, then the fastest.
+fn sort_by_points(scores: &mut [Score], scoring: &dyn ScoringModel) {
+    scores.sort_by_key(|score| (Reverse(scoring.points(score)), score.elapsed));
+}
+
+// Seconds since the Unix epoch as YYYY-MM-DD (UTC), using the days-to-civil
+// algorithm from http://howardhinnant.github.io/date_algorithms.html
+fn format_date(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
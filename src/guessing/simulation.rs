@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::{Difficulty, GuessingGame, Outcome, Strategy};
+
+/// Games without an attempt limit are given up after this many guesses, so a
+/// strategy that never finds the secret cannot hang the simulation.
+pub const GIVE_UP_AFTER: u32 = 100_000;
+
+/// How a strategy did over many games on one difficulty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationReport {
+    pub strategy: String,
+    pub difficulty: Difficulty,
+    pub games: u32,
+    /// Number of won games by the number of attempts they took.
+    pub wins_by_attempts: BTreeMap<u32, u32>,
+    pub losses: u32,
+}
+
+impl SimulationReport {
+    pub fn wins(&self) -> u32 {
+        self.wins_by_attempts.values().sum()
+    }
+
+    /// Share of games won, from 0.0 to 1.0.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        f64::from(self.wins()) / f64::from(self.games)
+    }
+
+    /// Average number of attempts over the won games.
+    pub fn mean_attempts(&self) -> Option<f64> {
+        let wins = self.wins();
+        if wins == 0 {
+            return None;
+        }
+        let total: u64 = self
+            .wins_by_attempts
+            .iter()
+            .map(|(attempts, count)| u64::from(*attempts) * u64::from(*count))
+            .sum();
+        Some(total as f64 / f64::from(wins))
+    }
+
+    /// Most attempts any won game took.
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.wins_by_attempts.keys().next_back().copied()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} on {}: won {} of {} games ({:.1}%)",
+            self.strategy,
+            self.difficulty,
+            self.wins(),
+            self.games,
+            self.win_rate() * 100.0
+        )?;
+        if let (Some(mean), Some(max)) = (self.mean_attempts(), self.max_attempts()) {
+            writeln!(f, "    attempts: mean {:.2}, max {}", mean, max)?;
+        }
+
+        // One bar per attempt count, the longest one 40 characters wide.
+        let most = self.wins_by_attempts.values().copied().max().unwrap_or(0);
+        for (attempts, count) in &self.wins_by_attempts {
+            let width = (u64::from(*count) * 40).div_ceil(u64::from(most)) as usize;
+            writeln!(
+                f,
+                "    {:>5} | {:<40} {}",
+                attempts,
+                "#".repeat(width),
+                count
+            )?;
+        }
+        if self.losses > 0 {
+            writeln!(f, "     lost | {}", self.losses)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays `games` games of `difficulty` with `strategy`. The secrets come from
+/// a generator seeded with `seed`, so two runs with the same seed play the
+/// same secrets. Bots that pick guesses at random should be seeded
+/// differently, or they will guess the secrets in one go.
+pub fn simulate(
+    strategy: &mut dyn Strategy,
+    difficulty: &Difficulty,
+    games: u32,
+    seed: u64,
+) -> SimulationReport {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut report = SimulationReport {
+        strategy: strategy.name().to_string(),
+        difficulty: difficulty.clone(),
+        games,
+        wins_by_attempts: BTreeMap::new(),
+        losses: 0,
+    };
+
+    for _ in 0..games {
+        let max_attempts = difficulty.max_attempts().or(Some(GIVE_UP_AFTER));
+        let mut game =
+            GuessingGame::with_difficulty(&mut rng, difficulty).with_max_attempts(max_attempts);
+        strategy.start(game.range().clone());
+
+        while !game.is_over() {
+            let guess = strategy.next_guess();
+            let ordering = match game.guess_number(guess) {
+                Outcome::TooSmall => Ordering::Less,
+                Outcome::TooBig => Ordering::Greater,
+                Outcome::Win => Ordering::Equal,
+                Outcome::Lost | Outcome::Invalid => break,
+            };
+            strategy.feedback(guess, ordering);
+        }
+
+        if game.is_won() {
+            *report.wins_by_attempts.entry(game.attempts()).or_insert(0) += 1;
+        } else {
+            report.losses += 1;
+        }
+    }
+    report
+}
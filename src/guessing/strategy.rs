@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A player of the guessing game.
+///
+/// The feedback is the same `Ordering` that `guess_game_v3` matches on:
+/// `guess.cmp(&secret)`, so `Less` means the guess was too small.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// Called before every game with the range the secret is picked from.
+    fn start(&mut self, range: RangeInclusive<u32>);
+
+    fn next_guess(&mut self) -> u32;
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering);
+}
+
+// The values the secret can still have, given the feedback so far.
+#[derive(Debug, Clone)]
+struct Interval {
+    low: u32,
+    high: u32,
+}
+
+impl Interval {
+    fn new(range: &RangeInclusive<u32>) -> Self {
+        Self {
+            low: *range.start(),
+            high: *range.end(),
+        }
+    }
+
+    fn narrow(&mut self, guess: u32, ordering: Ordering) {
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess.saturating_add(1)),
+            Ordering::Greater => self.high = self.high.min(guess.saturating_sub(1)),
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+            }
+        }
+        // Feedback that contradicts earlier feedback leaves an empty interval;
+        // keep guessing at its edge rather than panicking.
+        if self.low > self.high {
+            self.high = self.low;
+        }
+    }
+
+    fn midpoint(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+}
+
+/// Always guesses the middle of the values that are still possible, so it
+/// needs at most ⌈log2(n + 1)⌉ guesses for a range of n numbers.
+#[derive(Debug, Clone)]
+pub struct BinarySearch {
+    interval: Interval,
+}
+
+impl BinarySearch {
+    pub fn new() -> Self {
+        Self {
+            interval: Interval::new(&(0..=0)),
+        }
+    }
+}
+
+impl Default for BinarySearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &str {
+        "binary"
+    }
+
+    fn start(&mut self, range: RangeInclusive<u32>) {
+        self.interval = Interval::new(&range);
+    }
+
+    fn next_guess(&mut self) -> u32 {
+        self.interval.midpoint()
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+/// Guesses uniformly at random among the values that are still possible.
+#[derive(Debug, Clone)]
+pub struct RandomGuess {
+    interval: Interval,
+    rng: StdRng,
+}
+
+impl RandomGuess {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            interval: Interval::new(&(0..=0)),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn start(&mut self, range: RangeInclusive<u32>) {
+        self.interval = Interval::new(&range);
+    }
+
+    fn next_guess(&mut self) -> u32 {
+        self.rng.gen_range(self.interval.low..=self.interval.high)
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+/// Plays roughly like a person: aims for the middle but misses it by a random
+/// amount, prefers round numbers while the range is wide, and now and then
+/// forgets the latest feedback.
+#[derive(Debug, Clone)]
+pub struct HumanLike {
+    interval: Interval,
+    rng: StdRng,
+}
+
+impl HumanLike {
+    /// How often the latest feedback is forgotten.
+    const FORGET_CHANCE: f64 = 0.1;
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            interval: Interval::new(&(0..=0)),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for HumanLike {
+    fn name(&self) -> &str {
+        "human"
+    }
+
+    fn start(&mut self, range: RangeInclusive<u32>) {
+        self.interval = Interval::new(&range);
+    }
+
+    fn next_guess(&mut self) -> u32 {
+        let Interval { low, high } = self.interval;
+        let width = high - low;
+
+        // Miss the middle by up to a quarter of the width either way.
+        let spread = (width / 4) as i64;
+        let noise = self.rng.gen_range(-spread..=spread);
+        let mut guess = (self.interval.midpoint() as i64 + noise) as u32;
+
+        // "Let's say 50" rather than 47 while there is plenty of room.
+        if width > 20 {
+            guess = (guess + 2) / 5 * 5;
+        }
+        guess.clamp(low, high)
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        if ordering != Ordering::Equal && self.rng.gen_bool(Self::FORGET_CHANCE) {
+            return;
+        }
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+/// Names accepted by [`strategy_by_name`].
+pub const STRATEGIES: [&str; 3] = ["binary", "random", "human"];
+
+/// The strategy called `name`, seeding it with `seed` if it needs randomness.
+pub fn strategy_by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "binary" => Some(Box::new(BinarySearch::new())),
+        "random" => Some(Box::new(RandomGuess::new(seed))),
+        "human" => Some(Box::new(HumanLike::new(seed))),
+        _ => None,
+    }
+}
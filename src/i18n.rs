@@ -0,0 +1,238 @@
+//! Messages shown to players, looked up by id in the catalog of the chosen
+//! language, with English for anything not translated. The catalog in use is
+//! set once per thread with [`set_messages`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Where catalogs that are not built in are looked for.
+pub const DEFAULT_LOCALE_DIR: &str = "locales";
+
+/// The language every other one falls back to.
+pub const ENGLISH: &str = "en";
+
+// Built in, so that the program has its messages wherever it is run from.
+const ENGLISH_CATALOG: &str = include_str!("../locales/en.txt");
+const BUILTIN_CATALOGS: &[(&str, &str)] = &[
+    (ENGLISH, ENGLISH_CATALOG),
+    ("es", include_str!("../locales/es.txt")),
+];
+
+/// The messages of one language, by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    language: String,
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// The built-in English catalog.
+    pub fn english() -> Self {
+        Self::parse(ENGLISH, ENGLISH_CATALOG).expect("the English catalog is valid")
+    }
+
+    /// Reads a catalog from `text`: one `id = text` line per message, with
+    /// blank lines and lines starting with `#` skipped.
+    pub fn parse(language: &str, text: &str) -> Result<Self, CatalogError> {
+        let mut messages = HashMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = |reason: &str| CatalogError::Parse {
+                line: index + 1,
+                reason: reason.to_string(),
+            };
+            let (id, message) = line
+                .split_once('=')
+                .ok_or_else(|| parse_error("expected 'id = text'"))?;
+            let id = id.trim();
+            if id.is_empty() || id.contains(char::is_whitespace) {
+                return Err(parse_error("the id must be one word"));
+            }
+            if messages
+                .insert(id.to_string(), message.trim().to_string())
+                .is_some()
+            {
+                return Err(parse_error(&format!("'{}' is defined twice", id)));
+            }
+        }
+        Ok(Self {
+            language: language.to_string(),
+            messages,
+        })
+    }
+
+    /// The catalog shipped with the program for `language`, if there is one.
+    pub fn builtin(language: &str) -> Option<Self> {
+        let (language, text) = BUILTIN_CATALOGS
+            .iter()
+            .find(|(builtin, _)| *builtin == language)?;
+        Some(Self::parse(language, text).expect("the built-in catalogs are valid"))
+    }
+
+    /// Reads `<dir>/<language>.txt`. English always comes from the built-in
+    /// catalog.
+    pub fn load(dir: impl AsRef<Path>, language: &str) -> Result<Self, CatalogError> {
+        if language == ENGLISH {
+            return Ok(Self::english());
+        }
+        // The language is part of a file name, so only plain codes like "es"
+        // or "pt-BR" are accepted.
+        if language.is_empty()
+            || !language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(CatalogError::UnknownLanguage(language.to_string()));
+        }
+        let path = dir.as_ref().join(format!("{}.txt", language));
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Err(CatalogError::UnknownLanguage(language.to_string()))
+            }
+            Err(error) => return Err(CatalogError::Io(path, error)),
+        };
+        Self::parse(language, &text)
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+
+    /// Every id in the catalog, sorted.
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.messages.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    /// There is no catalog for the language.
+    UnknownLanguage(String),
+    Io(PathBuf, io::Error),
+    /// `line` counts from 1.
+    Parse { line: usize, reason: String },
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CatalogError::UnknownLanguage(language) => {
+                write!(f, "there are no messages for '{}'", language)
+            }
+            CatalogError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            CatalogError::Parse { line, reason } => write!(f, "line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// A catalog with English to fall back to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    catalog: Catalog,
+    english: Catalog,
+}
+
+impl Messages {
+    pub fn new(catalog: Catalog) -> Self {
+        Self {
+            catalog,
+            english: Catalog::english(),
+        }
+    }
+
+    pub fn english() -> Self {
+        Self::new(Catalog::english())
+    }
+
+    pub fn load(dir: impl AsRef<Path>, language: &str) -> Result<Self, CatalogError> {
+        Catalog::load(dir, language).map(Self::new)
+    }
+
+    /// The built-in catalog for `language`, or else `<dir>/<language>.txt`.
+    pub fn builtin_or_load(dir: impl AsRef<Path>, language: &str) -> Result<Self, CatalogError> {
+        match Catalog::builtin(language) {
+            Some(catalog) => Ok(Self::new(catalog)),
+            None => Self::load(dir, language),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        self.catalog.language()
+    }
+
+    /// The message, in English if it is not translated, or `None` if there
+    /// is no such message at all.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.catalog.get(id).or_else(|| self.english.get(id))
+    }
+
+    /// English messages this language has no translation for.
+    pub fn untranslated(&self) -> Vec<&str> {
+        self.english
+            .ids()
+            .into_iter()
+            .filter(|id| self.catalog.get(id).is_none())
+            .collect()
+    }
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+thread_local! {
+    static MESSAGES: RefCell<Rc<Messages>> = RefCell::new(Rc::new(Messages::english()));
+}
+
+/// Uses `messages` for everything the current thread shows from now on.
+pub fn set_messages(messages: Messages) {
+    MESSAGES.with(|current| *current.borrow_mut() = Rc::new(messages));
+}
+
+/// The messages the current thread uses.
+pub fn messages() -> Rc<Messages> {
+    MESSAGES.with(|current| Rc::clone(&current.borrow()))
+}
+
+/// The message `id`. An id that is in no catalog is returned as it is, so a
+/// typo shows up on screen rather than as a crash.
+pub fn text(id: &str) -> String {
+    messages().get(id).unwrap_or(id).to_string()
+}
+
+/// The message `id` with every `{name}` replaced by the value given for it.
+pub fn format(id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut message = text(id);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+/// Like [`format()`], for a message that depends on a number: `<id>.one` when
+/// `count` is 1 and `<id>.other` otherwise, with `{count}` filled in too.
+pub fn plural(id: &str, count: u64, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let form = if count == 1 { "one" } else { "other" };
+    let mut all: Vec<(&str, &dyn fmt::Display)> = vec![("count", &count)];
+    all.extend_from_slice(args);
+    format(&format!("{}.{}", id, form), &all)
+}
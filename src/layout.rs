@@ -0,0 +1,214 @@
+//! Sizes and alignments of the primitives and of the chapters' types, with
+//! the padding and niches that explain them.
+
+use std::fmt;
+use std::mem::{align_of, size_of};
+use std::num::NonZeroU32;
+
+use crate::part07::{PoisonType, SeaCreature, SeaCreaturesV2, Size, Species, Weapon};
+use crate::part09::WebEvent;
+
+/// One type, measured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    /// For enums: whether the tag is kept in a niche of the data, so that
+    /// the enum is no bigger than its largest variant.
+    pub niche: Option<bool>,
+    /// Where the size comes from, when it is not obvious.
+    pub note: Option<String>,
+}
+
+impl Row {
+    fn of<T>(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            niche: None,
+            note: None,
+        }
+    }
+
+    fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    // Compares the size with the fields it is made of.
+    fn fields(self, sizes: &[usize]) -> Self {
+        let total: usize = sizes.iter().sum();
+        let padding = self.size - total;
+        let note = match padding {
+            0 => format!("fields take {} bytes, no padding", total),
+            padding => format!("fields take {} bytes, {} of padding", total, padding),
+        };
+        self.note(note)
+    }
+
+    // Compares the size of an enum with its largest variant, given the sizes
+    // of each variant's fields. The fields are added up without padding: a
+    // tag could go in padding bytes, so only an enum no bigger than its
+    // fields is sure to keep the tag in a niche.
+    fn variants(mut self, variants: &[&[usize]]) -> Self {
+        let largest = variants
+            .iter()
+            .map(|fields| fields.iter().sum())
+            .max()
+            .unwrap_or(0);
+        self.niche = Some(self.size <= largest);
+        let note = match self.size.saturating_sub(largest) {
+            0 => format!("largest variant {} bytes; the tag fits in a niche", largest),
+            extra => format!(
+                "largest variant {} bytes, {} more for the tag and padding",
+                largest, extra
+            ),
+        };
+        self.note(note)
+    }
+
+    // An `Option<T>` row, compared with `T`.
+    fn option<T>(inner: &str) -> Self {
+        let mut row = Self::of::<Option<T>>(&format!("Option<{}>", inner));
+        row.niche = Some(row.size == size_of::<T>());
+        let note = match row.size - size_of::<T>() {
+            0 => format!(
+                "niche: as small as {}, None is a value it cannot hold",
+                inner
+            ),
+            extra => format!("no niche: {} bytes more than {} for the tag", extra, inner),
+        };
+        row.note(note)
+    }
+}
+
+/// Rows under a heading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub title: &'static str,
+    pub rows: Vec<Row>,
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("type".len());
+        writeln!(f, "{}", self.title)?;
+        write!(f, "  {:<width$}  {:>4}  {:>5}", "type", "size", "align")?;
+        for row in &self.rows {
+            write!(
+                f,
+                "\n  {:<width$}  {:>4}  {:>5}",
+                row.name, row.size, row.align
+            )?;
+            if let Some(note) = &row.note {
+                write!(f, "  {}", note)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every type, measured on this machine. Sizes of pointers and `usize`
+/// depend on it; the layout of structs and enums is up to the compiler, so
+/// the notes say what it chose rather than what it must do.
+pub fn report() -> Vec<Section> {
+    vec![
+        Section {
+            title: "Primitives",
+            rows: vec![
+                Row::of::<i8>("i8"),
+                Row::of::<i16>("i16"),
+                Row::of::<i32>("i32"),
+                Row::of::<i64>("i64"),
+                Row::of::<i128>("i128"),
+                Row::of::<isize>("isize").note("as wide as a pointer"),
+                Row::of::<u8>("u8"),
+                Row::of::<u16>("u16"),
+                Row::of::<u32>("u32"),
+                Row::of::<u64>("u64"),
+                Row::of::<u128>("u128"),
+                Row::of::<usize>("usize").note("as wide as a pointer"),
+                Row::of::<f32>("f32"),
+                Row::of::<f64>("f64"),
+                Row::of::<bool>("bool").note("one byte, of which only 0 and 1 are valid"),
+                Row::of::<char>("char").note("any scalar value up to U+10FFFF"),
+                Row::of::<()>("()").note("takes no memory at all"),
+            ],
+        },
+        Section {
+            title: "Compound types (part03)",
+            rows: vec![
+                Row::of::<(i32, f64, u8)>("(i32, f64, u8)").fields(&[
+                    size_of::<i32>(),
+                    size_of::<f64>(),
+                    size_of::<u8>(),
+                ]),
+                Row::of::<[i32; 5]>("[i32; 5]").note("5 × 4 bytes, no padding between elements"),
+            ],
+        },
+        Section {
+            title: "Pointers",
+            rows: vec![
+                Row::of::<&i32>("&i32"),
+                Row::of::<&str>("&str").note("a pointer and a length"),
+                Row::of::<&[i32]>("&[i32]").note("a pointer and a length"),
+                Row::of::<Box<i32>>("Box<i32>"),
+                Row::of::<String>("String").note("a pointer, a capacity and a length"),
+                Row::of::<Vec<i32>>("Vec<i32>").note("a pointer, a capacity and a length"),
+            ],
+        },
+        Section {
+            title: "Chapter types (part07, part09)",
+            rows: vec![
+                Row::of::<Species>("Species").note("4 variants without data: just a tag"),
+                Row::of::<SeaCreature>("SeaCreature").fields(&[
+                    size_of::<Species>(),
+                    size_of::<String>(),
+                    size_of::<i32>(),
+                    size_of::<i32>(),
+                    size_of::<String>(),
+                ]),
+                Row::of::<Weapon>("Weapon").variants(&[
+                    &[size_of::<i32>(), size_of::<Size>()],
+                    &[size_of::<PoisonType>()],
+                    &[],
+                ]),
+                Row::of::<SeaCreaturesV2>("SeaCreaturesV2").fields(&[
+                    size_of::<Species>(),
+                    size_of::<String>(),
+                    size_of::<i32>(),
+                    size_of::<i32>(),
+                    size_of::<Weapon>(),
+                ]),
+                Row::of::<WebEvent>("WebEvent").variants(&[
+                    &[],
+                    &[],
+                    &[size_of::<char>()],
+                    &[size_of::<String>()],
+                    &[size_of::<i64>(), size_of::<i64>()],
+                ]),
+            ],
+        },
+        Section {
+            title: "Options",
+            rows: vec![
+                Row::option::<i32>("i32"),
+                Row::option::<&i32>("&i32"),
+                Row::option::<Box<i32>>("Box<i32>"),
+                Row::option::<NonZeroU32>("NonZeroU32"),
+                Row::option::<bool>("bool"),
+                Row::option::<char>("char"),
+                Row::option::<String>("String"),
+                Row::option::<Weapon>("Weapon"),
+            ],
+        },
+    ]
+}
@@ -0,0 +1,56 @@
+//! Learning Rust programming using the official Rust book.
+//!
+//! Every chapter lives in its own `partNN` module and implements
+//! [`chapter::Chapter`]. The handful of functions and types from the chapters
+//! that are useful on their own are re-exported below, grouped by topic.
+
+pub mod alloc_trace;
+pub mod bigint;
+pub mod chapter;
+pub mod chars;
+pub mod cli;
+pub mod duration;
+pub mod guessing;
+pub mod i18n;
+pub mod layout;
+pub mod literals;
+pub mod numbers;
+pub mod overflow;
+pub mod rules;
+pub mod sequences;
+pub mod traced;
+pub mod units;
+
+// `pub mod partNN;` for every chapter file plus `builtin_chapters()`, see build.rs.
+// The lints that the chapters' examples trip on are allowed there, and only there.
+include!(concat!(env!("OUT_DIR"), "/chapters.rs"));
+
+/// Moving values into and out of functions, traced (chapter 04).
+pub mod ownership {
+    pub use crate::part04::{gives_ownership, takes_and_gives_back, takes_ownership};
+}
+
+/// Borrowing parts of a collection (chapter 05).
+pub mod slices {
+    pub use crate::part05::first_word;
+}
+
+/// Structs and their methods (chapter 06).
+pub mod structs {
+    pub use crate::part06::Rectangle;
+}
+
+/// Enums and `Option` (chapter 07).
+pub mod enums {
+    pub use crate::part07::add_one;
+}
+
+/// Recoverable errors with `Result` and `?` (chapter 10).
+pub mod errors {
+    pub use crate::part10::{last_char_of_first_line, read_username_from_file_v2};
+}
+
+/// Generic functions (chapter 11).
+pub mod generics {
+    pub use crate::part11::largest;
+}
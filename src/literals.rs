@@ -0,0 +1,346 @@
+//! Rust integer literals as written in chapter 03, like `0xff`, `0b1111_0000`
+//! or `-1i16`: parsed, checked against their type and shown in every base.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The integer types, with `isize` and `usize` as wide as on this machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+            IntType::I128 | IntType::U128 => 128,
+            IntType::Isize | IntType::Usize => usize::BITS,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8
+                | IntType::I16
+                | IntType::I32
+                | IntType::I64
+                | IntType::I128
+                | IntType::Isize
+        )
+    }
+
+    /// The largest value of the type.
+    pub fn max(self) -> u128 {
+        let value_bits = if self.is_signed() {
+            self.bits() - 1
+        } else {
+            self.bits()
+        };
+        u128::MAX >> (128 - value_bits)
+    }
+
+    /// How far below zero the type goes: 0 for unsigned types, and one more
+    /// than `max` for signed ones.
+    pub fn min_magnitude(self) -> u128 {
+        if self.is_signed() {
+            self.max() + 1
+        } else {
+            0
+        }
+    }
+
+    // Every bit of the type set.
+    fn mask(self) -> u128 {
+        u128::MAX >> (128 - self.bits())
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for IntType {
+    type Err = LiteralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IntType::ALL
+            .into_iter()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| LiteralError::UnknownType(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+impl Base {
+    pub const ALL: [Base; 4] = [Base::Decimal, Base::Hex, Base::Octal, Base::Binary];
+
+    pub fn radix(self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hex => 16,
+        }
+    }
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Base::Binary => "0b",
+            Base::Octal => "0o",
+            Base::Decimal => "",
+            Base::Hex => "0x",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Base::Binary => "binary",
+            Base::Octal => "octal",
+            Base::Decimal => "decimal",
+            Base::Hex => "hex",
+        }
+    }
+}
+
+/// A literal as written, before its type is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Literal {
+    pub negative: bool,
+    /// The value without its sign.
+    pub magnitude: u128,
+    pub base: Base,
+    pub suffix: Option<IntType>,
+}
+
+/// Parses a literal like `0xff`, `1_000_000`, `255u8` or `-0b1000_0000_i8`.
+///
+/// The minus sign is not part of a Rust literal but an operator applied to
+/// it; it is accepted here so that negative values can be written.
+pub fn parse_literal(input: &str) -> Result<Literal, LiteralError> {
+    let text = input.trim();
+    if text.is_empty() {
+        return Err(LiteralError::Empty);
+    }
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+
+    let (base, text) = if let Some(rest) = text.strip_prefix("0x") {
+        (Base::Hex, rest)
+    } else if let Some(rest) = text.strip_prefix("0o") {
+        (Base::Octal, rest)
+    } else if let Some(rest) = text.strip_prefix("0b") {
+        (Base::Binary, rest)
+    } else {
+        (Base::Decimal, text)
+    };
+
+    // Suffixes start with `i` or `u`, which are no digit in any base.
+    let (digits, suffix) = match text.find(['i', 'u']) {
+        Some(start) => (&text[..start], Some(text[start..].parse::<IntType>()?)),
+        None => (text, None),
+    };
+
+    // Without a prefix the literal must start with a digit: `_1` is a name.
+    if base == Base::Decimal && digits.starts_with('_') {
+        return Err(LiteralError::NoDigits);
+    }
+    let mut magnitude: u128 = 0;
+    let mut any_digit = false;
+    for c in digits.chars().filter(|&c| c != '_') {
+        let digit = c
+            .to_digit(base.radix())
+            .ok_or(LiteralError::InvalidDigit { digit: c, base })?;
+        magnitude = magnitude
+            .checked_mul(u128::from(base.radix()))
+            .and_then(|m| m.checked_add(u128::from(digit)))
+            .ok_or(LiteralError::TooLarge)?;
+        any_digit = true;
+    }
+    if !any_digit {
+        return Err(LiteralError::NoDigits);
+    }
+
+    Ok(Literal {
+        negative: negative && magnitude != 0,
+        magnitude,
+        base,
+        suffix,
+    })
+}
+
+/// Parses `input` and checks that it fits its type: the suffix's, or
+/// `unsuffixed` if it has none. Rust uses `i32` when nothing else decides.
+pub fn convert(input: &str, unsuffixed: IntType) -> Result<Value, LiteralError> {
+    let literal = parse_literal(input)?;
+    let ty = literal.suffix.unwrap_or(unsuffixed);
+    let fits = if literal.negative {
+        literal.magnitude <= ty.min_magnitude()
+    } else {
+        literal.magnitude <= ty.max()
+    };
+    if !fits {
+        return Err(LiteralError::DoesNotFit(ty));
+    }
+    Ok(Value {
+        ty,
+        negative: literal.negative,
+        magnitude: literal.magnitude,
+    })
+}
+
+/// A value known to fit its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value {
+    pub ty: IntType,
+    pub negative: bool,
+    pub magnitude: u128,
+}
+
+impl Value {
+    /// The value in `base` with its prefix, like `-0x1`.
+    pub fn in_base(&self, base: Base) -> String {
+        let sign = if self.negative { "-" } else { "" };
+        let digits = match base {
+            Base::Binary => format!("{:b}", self.magnitude),
+            Base::Octal => format!("{:o}", self.magnitude),
+            Base::Decimal => format!("{}", self.magnitude),
+            Base::Hex => format!("{:x}", self.magnitude),
+        };
+        format!("{}{}{}", sign, base.prefix(), digits)
+    }
+
+    /// The bits the value is stored as. Negative numbers are stored in two's
+    /// complement: the bits of the magnitude inverted, plus one.
+    pub fn bits(&self) -> u128 {
+        let bits = if self.negative {
+            (!self.magnitude).wrapping_add(1)
+        } else {
+            self.magnitude
+        };
+        bits & self.ty.mask()
+    }
+
+    /// Every bit of the type, most significant first, in groups of four.
+    pub fn bit_pattern(&self) -> String {
+        let bits = format!("{:0width$b}", self.bits(), width = self.ty.bits() as usize);
+        let groups: Vec<&str> = bits
+            .as_bytes()
+            .chunks(4)
+            .map(|group| std::str::from_utf8(group).expect("binary digits are ASCII"))
+            .collect();
+        groups.join("_")
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.in_base(Base::Decimal), self.ty)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    Empty,
+    NoDigits,
+    InvalidDigit {
+        digit: char,
+        base: Base,
+    },
+    UnknownType(String),
+    /// More than even `u128` holds.
+    TooLarge,
+    DoesNotFit(IntType),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::Empty => write!(f, "the literal is empty"),
+            LiteralError::NoDigits => write!(f, "the literal has no digits"),
+            LiteralError::InvalidDigit { digit, base } => {
+                write!(f, "'{}' is not a {} digit", digit, base.name())
+            }
+            LiteralError::UnknownType(suffix) => write!(
+                f,
+                "unknown integer type '{}', expected one of i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize",
+                suffix
+            ),
+            LiteralError::TooLarge => write!(f, "the literal is too large for any integer type"),
+            LiteralError::DoesNotFit(ty) => {
+                let min = if ty.is_signed() {
+                    format!("-{}", ty.min_magnitude())
+                } else {
+                    String::from("0")
+                };
+                write!(
+                    f,
+                    "the value does not fit in {}, which goes from {} to {}",
+                    ty,
+                    min,
+                    ty.max()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
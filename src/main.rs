@@ -1,37 +1,14 @@
-#![allow(
-    dead_code,
-    unused_imports,
-    unused_variables,
-    unused_mut,
-    unreachable_code
-)]
+use hello_rust::chapter::Registry;
+use hello_rust::cli;
 
-mod part01;
-mod part02;
-mod part03;
-mod part04;
-mod part05;
-mod part06;
-mod part07;
-mod part08;
-mod part09;
-mod part10;
-mod part11;
-mod part12;
-mod part13;
+// Counts every allocation, for `hello-rust trace`; off by default since it
+// slows every allocation down a little.
+#[cfg(feature = "alloc-trace")]
+#[global_allocator]
+static TRACER: hello_rust::alloc_trace::Tracer = hello_rust::alloc_trace::Tracer;
 
 fn main() {
-    // part01::run();
-    // part02::run();
-    // part03::run();
-    // part04::run();
-    // part05::run();
-    // part06::run();
-    // part07::run();
-    // part08::run();
-    // part09::run();
-    // part10::run();
-    // part11::run();
-    // part12::run();
-    part13::run();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let registry = Registry::builtin();
+    std::process::exit(cli::main(&registry, &args));
 }
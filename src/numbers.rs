@@ -0,0 +1,52 @@
+//! Small helpers on whole numbers that the games and the rule sets share:
+//! divisibility, and ranges written like `1..=500`.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Whether `number % divisor == 0`, except that a divisor of 0 does not
+/// panic. Answers the guessing game's hints and the rule sets' conditions.
+pub fn is_divisible(number: u32, divisor: u32) -> bool {
+    divisor != 0 && number % divisor == 0
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRangeError(String);
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid range '{}', expected two numbers like 1..=500 with the smaller one first",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+/// Parses "1..=500", "1..501" or "1-500" into an inclusive range.
+pub fn parse_range(s: &str) -> Result<RangeInclusive<u32>, ParseRangeError> {
+    let invalid = || ParseRangeError(s.to_string());
+    let (start, end, inclusive) = if let Some((start, end)) = s.split_once("..=") {
+        (start, end, true)
+    } else if let Some((start, end)) = s.split_once("..") {
+        (start, end, false)
+    } else if let Some((start, end)) = s.split_once('-') {
+        (start, end, true)
+    } else {
+        return Err(invalid());
+    };
+
+    let start: u32 = start.trim().parse().map_err(|_| invalid())?;
+    let end: u32 = end.trim().parse().map_err(|_| invalid())?;
+    let end = if inclusive {
+        end
+    } else {
+        end.checked_sub(1).ok_or_else(invalid)?
+    };
+    if start > end {
+        return Err(invalid());
+    }
+    Ok(start..=end)
+}
@@ -0,0 +1,559 @@
+//! What integer arithmetic does when the result does not fit the type: the
+//! `checked_`, `wrapping_`, `saturating_` and `overflowing_` methods side by
+//! side, each result from calling the real method.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::literals::{self, IntType, LiteralError, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Neg,
+    /// The exponent is a `u32`, whatever the type.
+    Pow,
+    /// The shift is a `u32`, whatever the type.
+    Shl,
+}
+
+impl Op {
+    pub const ALL: [Op; 8] = [
+        Op::Add,
+        Op::Sub,
+        Op::Mul,
+        Op::Div,
+        Op::Rem,
+        Op::Neg,
+        Op::Pow,
+        Op::Shl,
+    ];
+
+    /// The name used by the methods, as in `checked_add`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Neg => "neg",
+            Op::Pow => "pow",
+            Op::Shl => "shl",
+        }
+    }
+
+    pub fn operands(self) -> usize {
+        match self {
+            Op::Neg => 1,
+            _ => 2,
+        }
+    }
+
+    // How plain arithmetic is written, e.g. `a + b`.
+    fn expression(self, lhs: &str, rhs: &str) -> String {
+        match self {
+            Op::Add => format!("{} + {}", lhs, rhs),
+            Op::Sub => format!("{} - {}", lhs, rhs),
+            Op::Mul => format!("{} * {}", lhs, rhs),
+            Op::Div => format!("{} / {}", lhs, rhs),
+            Op::Rem => format!("{} % {}", lhs, rhs),
+            Op::Neg => format!("-({})", lhs),
+            Op::Pow => format!("{}.pow({})", lhs, rhs),
+            Op::Shl => format!("{} << {}", lhs, rhs),
+        }
+    }
+
+    // The message plain arithmetic panics with when it overflows.
+    fn overflow_message(self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            Op::Mul | Op::Pow => "attempt to multiply with overflow",
+            Op::Div => "attempt to divide with overflow",
+            Op::Rem => "attempt to calculate the remainder with overflow",
+            Op::Neg => "attempt to negate with overflow",
+            Op::Shl => "attempt to shift left with overflow",
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = ExploreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" | "+" => Ok(Op::Add),
+            "sub" | "-" => Ok(Op::Sub),
+            "mul" | "*" => Ok(Op::Mul),
+            "div" | "/" => Ok(Op::Div),
+            "rem" | "%" => Ok(Op::Rem),
+            "neg" => Ok(Op::Neg),
+            "pow" => Ok(Op::Pow),
+            "shl" | "<<" => Ok(Op::Shl),
+            other => Err(ExploreError::UnknownOp(other.to_string())),
+        }
+    }
+}
+
+/// What one method returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    Value(String),
+    /// A `checked_` method returned `None`.
+    Nothing,
+    /// The method panics with these operands, e.g. on division by zero.
+    Panics,
+    /// The type has no such method, like `saturating_neg` on unsigned types.
+    Missing,
+}
+
+impl Cell {
+    fn from_option<T: Display>(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Cell::Value(value.to_string()),
+            None => Cell::Nothing,
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cell::Value(value) => write!(f, "{}", value),
+            Cell::Nothing => write!(f, "None"),
+            Cell::Panics => write!(f, "panics"),
+            Cell::Missing => write!(f, "-"),
+        }
+    }
+}
+
+/// What plain arithmetic does, which depends on the build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plain {
+    pub debug: Cell,
+    pub release: Cell,
+    /// What it panics with, if it does.
+    pub panic: Option<&'static str>,
+}
+
+/// The results of every method for one operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ty: IntType,
+    pub op: Op,
+    pub operands: Vec<String>,
+    pub checked: Cell,
+    pub wrapping: Cell,
+    pub saturating: Cell,
+    /// The value `overflowing_` returns along with [`Report::overflowed`].
+    pub overflowing: Cell,
+    pub overflowed: bool,
+    /// `None` if the operator does not exist for the type (`-` on unsigned).
+    pub plain: Option<Plain>,
+}
+
+// What the methods returned, before the report is put together.
+struct Results<T> {
+    checked: Option<T>,
+    // `None` when the method panics.
+    wrapping: Option<T>,
+    saturating: Cell,
+    overflowing: Option<(T, bool)>,
+    // Division panics in release builds too.
+    always_panics: bool,
+}
+
+fn results<T: Display + Copy>(
+    checked: Option<T>,
+    wrapping: T,
+    saturating: Option<T>,
+    overflowing: (T, bool),
+) -> Results<T> {
+    Results {
+        checked,
+        wrapping: Some(wrapping),
+        saturating: match saturating {
+            Some(value) => Cell::Value(value.to_string()),
+            None => Cell::Missing,
+        },
+        overflowing: Some(overflowing),
+        always_panics: false,
+    }
+}
+
+// Division and remainder by zero: every method but the checked one panics.
+fn by_zero<T>() -> Results<T> {
+    Results {
+        checked: None,
+        wrapping: None,
+        saturating: Cell::Panics,
+        overflowing: None,
+        always_panics: true,
+    }
+}
+
+// Calls the methods of `$t`. `$lhs` and `$rhs` are bit patterns, so `as`
+// turns them into values of the type; shifts and powers take a `u32`.
+macro_rules! run {
+    ($t:ty, $op:expr, $lhs:expr, $rhs:expr, $saturating_neg:expr) => {{
+        let a = $lhs as $t;
+        let b = $rhs as $t;
+        let n = $rhs as u32;
+        let returned = match $op {
+            Op::Add => results(
+                a.checked_add(b),
+                a.wrapping_add(b),
+                Some(a.saturating_add(b)),
+                a.overflowing_add(b),
+            ),
+            Op::Sub => results(
+                a.checked_sub(b),
+                a.wrapping_sub(b),
+                Some(a.saturating_sub(b)),
+                a.overflowing_sub(b),
+            ),
+            Op::Mul => results(
+                a.checked_mul(b),
+                a.wrapping_mul(b),
+                Some(a.saturating_mul(b)),
+                a.overflowing_mul(b),
+            ),
+            Op::Div if b == 0 => by_zero(),
+            Op::Rem if b == 0 => Results {
+                saturating: Cell::Missing,
+                ..by_zero()
+            },
+            Op::Div => Results {
+                always_panics: true,
+                ..results(
+                    a.checked_div(b),
+                    a.wrapping_div(b),
+                    Some(a.saturating_div(b)),
+                    a.overflowing_div(b),
+                )
+            },
+            Op::Rem => Results {
+                always_panics: true,
+                ..results(
+                    a.checked_rem(b),
+                    a.wrapping_rem(b),
+                    None,
+                    a.overflowing_rem(b),
+                )
+            },
+            Op::Neg => results(
+                a.checked_neg(),
+                a.wrapping_neg(),
+                $saturating_neg(a),
+                a.overflowing_neg(),
+            ),
+            Op::Pow => results(
+                a.checked_pow(n),
+                a.wrapping_pow(n),
+                Some(a.saturating_pow(n)),
+                a.overflowing_pow(n),
+            ),
+            Op::Shl => results(
+                a.checked_shl(n),
+                a.wrapping_shl(n),
+                None,
+                a.overflowing_shl(n),
+            ),
+        };
+        report_from(returned)
+    }};
+}
+
+// The cells of a report, and whether the operation overflowed.
+type Cells = (Cell, Cell, Cell, Cell, bool, Option<Plain>);
+
+fn report_from<T: Display + Copy>(results: Results<T>) -> Cells {
+    let overflowed = results
+        .overflowing
+        .is_some_and(|(_, overflowed)| overflowed);
+    let value_or_panic = |value: Option<T>| match value {
+        Some(value) => Cell::Value(value.to_string()),
+        None => Cell::Panics,
+    };
+    let plain = if results.wrapping.is_none() {
+        Plain {
+            debug: Cell::Panics,
+            release: Cell::Panics,
+            panic: None,
+        }
+    } else if overflowed {
+        Plain {
+            debug: Cell::Panics,
+            release: if results.always_panics {
+                Cell::Panics
+            } else {
+                value_or_panic(results.wrapping)
+            },
+            panic: None,
+        }
+    } else {
+        let value = value_or_panic(results.wrapping);
+        Plain {
+            debug: value.clone(),
+            release: value,
+            panic: None,
+        }
+    };
+    (
+        Cell::from_option(results.checked),
+        value_or_panic(results.wrapping),
+        results.saturating,
+        value_or_panic(results.overflowing.map(|(value, _)| value)),
+        overflowed,
+        Some(plain),
+    )
+}
+
+/// The type the operands have without `--type`, as the compiler would pick
+/// it: the suffix of an operand, or `i32` if there is none. The right-hand
+/// side of `pow` and `shl` is always a `u32`, so it does not count.
+pub fn infer_type(op: Op, lhs: &str, rhs: Option<&str>) -> IntType {
+    let rhs = match op {
+        Op::Pow | Op::Shl => None,
+        _ => rhs,
+    };
+    [Some(lhs), rhs]
+        .into_iter()
+        .flatten()
+        .find_map(|operand| literals::parse_literal(operand).ok()?.suffix)
+        .unwrap_or(IntType::I32)
+}
+
+/// Runs `op` on `ty` with every family of methods. The operands are
+/// literals as read by [`literals::convert`]; a suffix must agree with `ty`.
+/// The right-hand side of `pow` and `shl` is a `u32`.
+pub fn explore(ty: IntType, op: Op, lhs: &str, rhs: Option<&str>) -> Result<Report, ExploreError> {
+    let operand = |text: &str, ty: IntType| -> Result<Value, ExploreError> {
+        let value = literals::convert(text, ty)
+            .map_err(|error| ExploreError::Literal(text.to_string(), error))?;
+        if value.ty != ty {
+            return Err(ExploreError::WrongType {
+                operand: text.to_string(),
+                expected: ty,
+            });
+        }
+        Ok(value)
+    };
+
+    let a = operand(lhs, ty)?;
+    let b = match (op.operands(), rhs) {
+        (1, None) => None,
+        (2, Some(rhs)) => {
+            let rhs_type = match op {
+                Op::Pow | Op::Shl => IntType::U32,
+                _ => ty,
+            };
+            Some(operand(rhs, rhs_type)?)
+        }
+        (expected, _) => return Err(ExploreError::Operands { op, expected }),
+    };
+
+    let (x, y) = (a.bits(), b.map_or(0, |b| b.bits()));
+    let (checked, wrapping, saturating, overflowing, overflowed, mut plain) = match ty {
+        IntType::I8 => run!(i8, op, x, y, |a: i8| Some(a.saturating_neg())),
+        IntType::I16 => run!(i16, op, x, y, |a: i16| Some(a.saturating_neg())),
+        IntType::I32 => run!(i32, op, x, y, |a: i32| Some(a.saturating_neg())),
+        IntType::I64 => run!(i64, op, x, y, |a: i64| Some(a.saturating_neg())),
+        IntType::I128 => run!(i128, op, x, y, |a: i128| Some(a.saturating_neg())),
+        IntType::Isize => run!(isize, op, x, y, |a: isize| Some(a.saturating_neg())),
+        IntType::U8 => run!(u8, op, x, y, |_| None::<u8>),
+        IntType::U16 => run!(u16, op, x, y, |_| None::<u16>),
+        IntType::U32 => run!(u32, op, x, y, |_| None::<u32>),
+        IntType::U64 => run!(u64, op, x, y, |_| None::<u64>),
+        IntType::U128 => run!(u128, op, x, y, |_| None::<u128>),
+        IntType::Usize => run!(usize, op, x, y, |_| None::<usize>),
+    };
+
+    if let Some(plain) = &mut plain {
+        let by_zero = matches!(op, Op::Div | Op::Rem) && y == 0;
+        plain.panic = if by_zero && op == Op::Div {
+            Some("attempt to divide by zero")
+        } else if by_zero {
+            Some("attempt to calculate the remainder with a divisor of zero")
+        } else if overflowed {
+            Some(op.overflow_message())
+        } else {
+            None
+        };
+    }
+    // There is no `-` for unsigned types.
+    if op == Op::Neg && !ty.is_signed() {
+        plain = None;
+    }
+
+    Ok(Report {
+        ty,
+        op,
+        operands: [Some(a), b]
+            .into_iter()
+            .flatten()
+            .map(|v| v.in_base(literals::Base::Decimal))
+            .collect(),
+        checked,
+        wrapping,
+        saturating,
+        overflowing,
+        overflowed,
+        plain,
+    })
+}
+
+impl Report {
+    /// The report as one JSON object. Numbers are written as JSON numbers, a
+    /// `None` from a checked method as `null`, a panic as `"panic"`, and
+    /// methods the type does not have are left out.
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            format!("\"type\":\"{}\"", self.ty),
+            format!("\"operation\":\"{}\"", self.op.name()),
+            format!("\"operands\":[{}]", self.operands.join(",")),
+        ];
+        let mut field = |name: &str, cell: &Cell| {
+            if let Some(json) = cell_json(cell) {
+                fields.push(format!("\"{}\":{}", name, json));
+            }
+        };
+        field("checked", &self.checked);
+        field("wrapping", &self.wrapping);
+        field("saturating", &self.saturating);
+        match &self.overflowing {
+            Cell::Value(value) => fields.push(format!(
+                "\"overflowing\":{{\"value\":{},\"overflowed\":{}}}",
+                value, self.overflowed
+            )),
+            cell => {
+                if let Some(json) = cell_json(cell) {
+                    fields.push(format!("\"overflowing\":{}", json));
+                }
+            }
+        }
+        if let Some(plain) = &self.plain {
+            let mut plain_fields = Vec::new();
+            for (name, cell) in [("debug", &plain.debug), ("release", &plain.release)] {
+                if let Some(json) = cell_json(cell) {
+                    plain_fields.push(format!("\"{}\":{}", name, json));
+                }
+            }
+            if let Some(message) = plain.panic {
+                plain_fields.push(format!("\"panic_message\":\"{}\"", message));
+            }
+            fields.push(format!("\"plain\":{{{}}}", plain_fields.join(",")));
+        }
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+fn cell_json(cell: &Cell) -> Option<String> {
+    match cell {
+        Cell::Value(value) => Some(value.clone()),
+        Cell::Nothing => Some(String::from("null")),
+        Cell::Panics => Some(String::from("\"panic\"")),
+        Cell::Missing => None,
+    }
+}
+
+// A table with one row per method, then the plain operator.
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lhs = &self.operands[0];
+        let rhs = self.operands.get(1).map_or("", String::as_str);
+        writeln!(f, "{} on {}", self.op.expression(lhs, rhs), self.ty)?;
+        let name = self.op.name();
+        let checked = match &self.checked {
+            Cell::Value(value) => format!("Some({})", value),
+            cell => cell.to_string(),
+        };
+        let overflowing = match &self.overflowing {
+            Cell::Value(value) => format!("({}, {})", value, self.overflowed),
+            cell => cell.to_string(),
+        };
+        let width = "overflowing_".len() + name.len() + 2;
+        writeln!(f, "{:<width$}{}", format!("checked_{}", name), checked)?;
+        writeln!(
+            f,
+            "{:<width$}{}",
+            format!("wrapping_{}", name),
+            self.wrapping
+        )?;
+        writeln!(
+            f,
+            "{:<width$}{}",
+            format!("saturating_{}", name),
+            self.saturating
+        )?;
+        writeln!(
+            f,
+            "{:<width$}{}",
+            format!("overflowing_{}", name),
+            overflowing
+        )?;
+
+        let plain = match &self.plain {
+            Some(plain) => plain,
+            None => return write!(f, "{:<width$}does not compile for {}", "plain", self.ty),
+        };
+        let panic = plain.panic.unwrap_or("");
+        match (&plain.debug, &plain.release) {
+            (Cell::Panics, Cell::Panics) => {
+                write!(f, "{:<width$}panics in every build: {}", "plain", panic)
+            }
+            (Cell::Panics, release) => write!(
+                f,
+                "{:<width$}panics in debug builds: {}; gives {} in release builds",
+                "plain", panic, release
+            ),
+            (debug, _) => write!(f, "{:<width$}{}", "plain", debug),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExploreError {
+    UnknownOp(String),
+    Literal(String, LiteralError),
+    /// A suffix names another type than the one explored.
+    WrongType {
+        operand: String,
+        expected: IntType,
+    },
+    Operands {
+        op: Op,
+        expected: usize,
+    },
+}
+
+impl Display for ExploreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExploreError::UnknownOp(op) => write!(
+                f,
+                "unknown operation '{}', expected add, sub, mul, div, rem, neg, pow or shl",
+                op
+            ),
+            ExploreError::Literal(operand, error) => {
+                write!(f, "invalid operand '{}': {}", operand, error)
+            }
+            ExploreError::WrongType { operand, expected } => {
+                write!(f, "the operand '{}' should be a {}", operand, expected)
+            }
+            ExploreError::Operands { op, expected: 1 } => {
+                write!(f, "'{}' takes one operand", op.name())
+            }
+            ExploreError::Operands { op, expected } => {
+                write!(f, "'{}' takes {} operands", op.name(), expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExploreError {}
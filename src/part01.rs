@@ -1,5 +1,34 @@
-pub fn run() {
-    println!(
+///////////////////////
+// Getting Started
+///////////////////////
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part01;
+
+impl Chapter for Part01 {
+    fn id(&self) -> &str {
+        "01"
+    }
+
+    fn title(&self) -> &str {
+        "Getting Started"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["installation", "hello world", "cargo"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
         "
         Use this command to install rust on linux:
             $ curl --proto '=https' --tlsv1.3 https://sh.rustup.rs -sSf | sh
@@ -13,7 +42,8 @@ pub fn run() {
         Compile and run rust program
             $ cargo run
         "
-    );
+    )?;
 
-    println!("Hello, World!");
+    writeln!(out, "Hello, World!")?;
+    Ok(())
 }
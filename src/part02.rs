@@ -1,26 +1,81 @@
-use std::io;
-
 ///////////////////////
 // Guessing Game
 ///////////////////////
 
-pub fn run() {
-    // guess_game_v1();
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
-    // Allow multiple guesses with looping
-    // guess_game_v2();
+use rand::RngCore;
+
+use crate::chapter::Chapter;
+use crate::guessing::{
+    play_turns, Clue, Difficulty, Game, GameSettings, GuessingGame, HintSettings, HintedGame,
+    Outcome, Recorder, Score, ScoreTable, Session,
+};
+use crate::i18n;
+
+pub struct Part02;
+
+impl Chapter for Part02 {
+    fn id(&self) -> &str {
+        "02"
+    }
+
+    fn title(&self) -> &str {
+        "Guessing Game"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["stdin", "rand", "match", "loop"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+
+    fn interactive(&self) -> bool {
+        true
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    // guess_game_v1(&mut input, out)?;
 
-    // Handling Invalid input
-    guess_game_v3();
+    // Allow multiple guesses with looping
+    // guess_game_v2(&mut input, out, &mut rand::thread_rng())?;
+
+    // Handling Invalid input, with a difficulty that limits the range and the
+    // number of guesses. Scores and replays are left to `hello-rust play`, so
+    // running the chapter writes no files.
+    let settings = GameSettings {
+        difficulty: Difficulty::prompt(&mut input, out)?,
+        reveal_secret: false,
+        score_file: None,
+        replay_dir: None,
+        hints: HintSettings::default(),
+    };
+    guess_game_v3(&mut input, out, &mut rand::thread_rng(), &settings)?;
+    Ok(())
 }
 
-fn guess_game_v1() {
+// The game itself, picking the secret number and comparing guesses with it,
+// lives in `crate::guessing::GuessingGame`. The functions below are front-ends:
+// they read guesses from `input` and print what the game answers. Taking the
+// input and the random number generator as parameters means a test can play
+// the game with a fixed script and a seeded generator.
+
+fn guess_game_v1(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
     // decalare variable to store user input
     // this is a mutable variable
     let mut guess = String::new();
 
     // take user input
-    io::stdin()
+    input
         .read_line(&mut guess)
         .expect("Failed to read line");
 
@@ -36,112 +91,168 @@ fn guess_game_v1() {
 
     // If the instance of `Result` type is an `Err` value, `expect` will cause
     // the program to crash.
-    println!("Your guess is {guess}");
-
-
+    writeln!(out, "Your guess is {guess}")?;
+    Ok(())
 }
 
-fn guess_game_v2() {
+fn guess_game_v2(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rng: &mut dyn RngCore,
+) -> io::Result<()> {
     // We want to generate random numbers for our guessing game.
     // Rust by default don't have any random number generator library, so
     // We will add one to our project. We can do it using Cargo.toml file.
     // Add following line to you `Cargo.toml` file dependency section.
     // `rand = "0.8.5"`
+    let mut game = GuessingGame::new(rng);
 
-    use rand::{thread_rng, Rng};
-    let secret_number = rand::thread_rng().gen_range(0..=100);
-
-    println!("The secret number is {secret_number}");
+    writeln!(out, "The secret number is {}", game.secret())?;
 
     // loop to take multiple user guesses
     loop {
-        println!("Please input your guess");
+        writeln!(out, "Please input your guess")?;
 
         let mut guess = String::new();
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        println!("You guess: {guess}");
-
-        // define a unsigned integer to take integer input
-        // this will overshadow the earlier defined guess variable
-        let guess: u32 = guess.trim().parse().expect("Plese enter a number!");
-
-        // We need to trim the user input as it contains unneccessary `\n`.
-        // This will cause parsing of input as integer to fail.
-
-        // The parse method on strings converts a string to another type.
-        // We need to tell Rust the exact number type we want by using `let guess: u32`
-        // Because it might fail, the parse method returns a Result type, much as
-        // the read_line method does.
-
-        // If parse returns an Err Result variant because it couldn’t create a
-        // number from the string, the `expect` call will crash the game and print
-        // the message we give it.
-
-        use std::cmp::Ordering;
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
+        if input.read_line(&mut guess)? == 0 {
+            return Ok(()); // no more input
+        }
+
+        writeln!(out, "You guess: {guess}")?;
+
+        // `game.guess` trims the input, since it contains an unneccessary `\n`
+        // that would make parsing it as an integer fail, and then parses it.
+        // Because parsing might fail, the game answers Outcome::Invalid for
+        // input that isn't a number.
+
+        // Here an invalid guess crashes the game with the message we give
+        // to panic!, just like calling `expect` on the Result of parse would.
+        match game.guess(&guess) {
+            Outcome::TooSmall => writeln!(out, "Too small")?,
+            Outcome::TooBig => writeln!(out, "Too big!")?,
+            Outcome::Win => {
+                writeln!(out, "You win!")?;
                 break; // quit if your guess is right
             }
+            Outcome::Invalid => panic!("Plese enter a number!"),
+            Outcome::Lost => unreachable!("this game has no limit on attempts"),
         }
     }
+    Ok(())
 }
 
-fn guess_game_v3() {
-    // We want to generate random numbers for our guessing game.
-    // Rust by default don't have any random number generator library, so
-    // We will add one to our project. We can do it using Cargo.toml file.
-    // Add following line to you `Cargo.toml` file dependency section.
-    // `rand = "0.8.5"`
-
-    use rand::{thread_rng, Rng};
-    let secret_number = rand::thread_rng().gen_range(0..=100);
-
-    println!("The secret number is {secret_number}");
-
-    // using loop to take multiple user guesses
-    loop {
-        println!("Please input your guess");
-
-        let mut guess = String::new();
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        println!("You guess: {guess}");
-
-        // Rathen than crashing the program when the user inputs a non-number,
-        // make the game ignore a non-number so that user can continue guessing.
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+pub fn guess_game_v3(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rng: &mut dyn RngCore,
+    settings: &GameSettings,
+) -> io::Result<()> {
+    // The secret is picked with a seed of its own, so that the session can be
+    // recorded and played again exactly (see `crate::guessing::Session`).
+    let session = Session::new(rng.next_u64(), &settings.difficulty);
+    let mut game = HintedGame::new(session.game(), settings.hints);
+    let mut recorder = match &settings.replay_dir {
+        Some(dir) => match Recorder::create(dir, session) {
+            Ok(recorder) => Some(recorder),
+            Err(error) => {
+                let path = dir.display();
+                let args: &[(&str, &dyn Display)] = &[("path", &path), ("error", &error)];
+                writeln!(out, "{}", i18n::format("game.not-recorded", args))?;
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Messages come from the catalog of the chosen language (see
+    // `crate::i18n`), with the numbers filled in by name.
+    let range = game.game().range();
+    let intro = i18n::format("game.intro", &[("start", range.start()), ("end", range.end())]);
+    writeln!(out, "{intro}")?;
+    if settings.reveal_secret {
+        let secret = game.game().secret();
+        writeln!(out, "{}", i18n::format("game.reveal", &[("secret", &secret)]))?;
+    }
+    if settings.hints.budget > 0 {
+        let budget = settings.hints.budget;
+        writeln!(out, "{}", i18n::format("game.hints", &[("budget", &budget)]))?;
+    }
+    let started = Instant::now();
+
+    // The loop that reads guesses and prints how they went is shared with the
+    // other games, like bulls and cows (see `crate::guessing::play_turns`);
+    // all this game adds is recording every guess, and the score once it is
+    // won.
+    play_turns(&mut game, input, out, &mut |guess, result, out| {
+        let outcome = match result {
+            Ok(Clue::Guess(outcome, _)) => *outcome,
+            // Hints leave the game as it was, so replays can do without them.
+            Ok(Clue::Answer { .. }) => return Ok(()),
+            Err(_) => Outcome::Invalid,
         };
+        record_guess(&mut recorder, out, started.elapsed(), guess, outcome)
+    })?;
 
-        // We need to trim the user input as it contains unneccessary `\n`.
-        // This will cause parsing of input as integer to fail.
-
-        // The parse method on strings converts a string to another type.
-        // We need to tell Rust the exact number type we want by using `let guess: u32`
-        // Because it might fail, the parse method returns a Result type, much as
-        // the read_line method does.
+    if game.is_won() {
+        if let Some(score_file) = &settings.score_file {
+            record_score(input, out, score_file, &game, started.elapsed())?;
+        }
+    }
 
-        // If parse returns an Err Result variant because it couldn’t create a
-        // number from the string, the `expect` call will crash the game and print
-        // the message we give it.
+    if let Some(recorder) = &recorder {
+        let path = recorder.path().display();
+        writeln!(out, "{}", i18n::format("game.recorded", &[("path", &path)]))?;
+    }
+    Ok(())
+}
 
-        use std::cmp::Ordering;
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break; // quit if your guess is right
-            }
+// Like a score, a replay log that cannot be written shouldn't end the game, so
+// recording stops with a message instead.
+fn record_guess(
+    recorder: &mut Option<Recorder>,
+    out: &mut dyn Write,
+    elapsed: Duration,
+    input: &str,
+    outcome: Outcome,
+) -> io::Result<()> {
+    if let Some(active) = recorder {
+        if let Err(error) = active.record(elapsed, input, outcome) {
+            let path = active.path().display();
+            let args: &[(&str, &dyn Display)] = &[("path", &path), ("error", &error)];
+            writeln!(out, "{}", i18n::format("game.not-recorded", args))?;
+            *recorder = None;
         }
     }
+    Ok(())
+}
+
+fn record_score(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    score_file: &Path,
+    game: &HintedGame,
+    elapsed: Duration,
+) -> io::Result<()> {
+    writeln!(out, "{}", i18n::text("score.name-prompt"))?;
+    let mut name = String::new();
+    input.read_line(&mut name)?;
+
+    let range = game.game().range().clone();
+    let score = Score::new(&name, game.attempts(), elapsed, range).with_hints(game.hints_used());
+
+    // A score that cannot be saved shouldn't spoil a won game, so problems
+    // with the file are reported rather than passed on.
+    let saved = ScoreTable::load(score_file).and_then(|mut table| {
+        let rank = table.add(score);
+        table.save().map(|()| rank)
+    });
+    let message = match saved {
+        Ok(Some(rank)) => i18n::format("score.rank", &[("rank", &rank)]),
+        Ok(None) => i18n::text("score.not-ranked"),
+        Err(error) => {
+            let path = score_file.display();
+            i18n::format("score.not-saved", &[("path", &path), ("error", &error)])
+        }
+    };
+    writeln!(out, "{message}")
 }
@@ -2,25 +2,52 @@
 // Common Programming Concepts
 ///////////////////////////////
 
-pub fn run() {
-    variables_and_mutability();
-    constants();
-    shadowing();
-    data_types();
-    functions();
-    control_flow();
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+use crate::duration::Span;
+
+pub struct Part03;
+
+impl Chapter for Part03 {
+    fn id(&self) -> &str {
+        "03"
+    }
+
+    fn title(&self) -> &str {
+        "Common Programming Concepts"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["variables", "constants", "shadowing", "data types", "functions", "control flow"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    variables_and_mutability(out)?;
+    constants(out)?;
+    shadowing(out)?;
+    data_types(out)?;
+    functions(out)?;
+    control_flow(out)?;
+    Ok(())
 }
 
-fn variables_and_mutability() {
-    println!("=========Inside Variables and Mutability=========");
+fn variables_and_mutability(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=========Inside Variables and Mutability=========")?;
     let mut x = 5;
-    println!("x is {x}");
+    writeln!(out, "x is {x}")?;
     x = 6; //possible because x is defined as mut
-    println!("x is {x}");
+    writeln!(out, "x is {x}")?;
+    Ok(())
 }
 
-fn constants() {
-    println!("=========Inside Constants=========");
+fn constants(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=========Inside Constants=========")?;
     // Constants are values that are bound to a name and are not allowed to 
     // change. The difference between immutable variables and constants are:
     // 1. You cannot use mut keyword wit constants.
@@ -30,37 +57,49 @@ fn constants() {
     // that could only be computed at runtime.
 
     const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
+    // The same, with the units named (see crate::duration); a span too long
+    // for a u64 of seconds would not compile.
+    const THREE_HOURS: Span = Span::from_hours(3);
+    writeln!(out, "{THREE_HOURS} is {THREE_HOURS_IN_SECONDS} seconds")?;
+    Ok(())
 }
 
-fn shadowing() {
+fn shadowing(out: &mut dyn Write) -> io::Result<()> {
     let x = 5;
     let x = x+1;
     {
         let x = x * 2;
-        println!("The value of x in the inner scope is {x}"); // 12
+        writeln!(out, "The value of x in the inner scope is {x}")?; // 12
     }
-    println!("The value of x is {x}"); // 6
+    writeln!(out, "The value of x is {x}")?; // 6
     
     // We can even change the type of variable
     let x = 6.7;
-    println!("The value of x is now {x}");
+    writeln!(out, "The value of x is now {x}")?;
+    Ok(())
 }
 
-fn data_types() {
-    println!("=========Inside Data Types=========");
+fn data_types(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=========Inside Data Types=========")?;
     // Rust being a statically typed language, it must know the types of all
     // variables at compile time.
     
     // Integers
     // i8, i16, i32, i64, i128, isize
     // u8. u16, u32, u64, u128, usize
-    println!("{}", 0xff); // 255
-    println!("{}", 0xff * 0o77); // 16065
-    println!("{}", 0b10111_011); // 187
+    writeln!(out, "{}", 0xff)?; // 255
+    writeln!(out, "{}", 0xff * 0o77)?; // 16065
+    writeln!(out, "{}", 0b10111_011)?; // 187
+    // `hello-rust convert 0b10111_011` shows such a literal in every base, and
+    // checks suffixed ones like 255u8 fit their type (see crate::literals).
+    // `hello-rust overflow add 255u8 1` shows what happens when arithmetic
+    // does not fit the type (see crate::overflow).
     
     // Floating points
     let x = 2.0; // f64 - default
     let y: f32 = 3.0;
+    // Floats with units attached, and a temperature converter, are in
+    // crate::units; `hello-rust units 98.6F` runs it.
     
     // Numeric operation
     let sum = 5+10; 
@@ -81,6 +120,7 @@ fn data_types() {
     // Unicode Scalar Values range from U+0000 to U+D7FF and U+E000 to U+10FFFF inclusive. 
     // However, a “character” isn’t really a concept in Unicode, so your human intuition 
     // for what a “character” is may not match up with what a char is in Rust.
+    // `hello-rust inspect-char ℤ` shows what a char is made of (see crate::chars).
     
     // Compound type
     
@@ -88,10 +128,10 @@ fn data_types() {
     let tuple:(i32, f64, u8) = (500, 6.4, 1);
     // Pattern matching in tuple
     let tup = (500, 6.4, 1);
-    println!("x is {}", tup.0);
+    writeln!(out, "x is {}", tup.0)?;
 
     let (x, y, z) = tuple; // destructuring
-    println!("y is {}", y);
+    writeln!(out, "y is {}", y)?;
     
     // Array type
     let a1 = [1, 2, 3, 4, 5];
@@ -99,22 +139,26 @@ fn data_types() {
     let a3 = [3; 5]; // 5 elements; all set to 3
     let first = a1[0];
     let second = a1[1];
+    Ok(())
 }
 
-fn functions() {
-    println!("=========Inside Functions=========");
-    another_function(5);
-    multiple_parameters(5, 'Z');
+fn functions(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=========Inside Functions=========")?;
+    another_function(out, 5)?;
+    multiple_parameters(out, 5, 'Z')?;
     let five = func_returning_value(4);
-    println!("Returned value is {five}");
+    writeln!(out, "Returned value is {five}")?;
+    Ok(())
 }
 
-fn another_function(x: i32) {
-    println!("x is {x}");
+fn another_function(out: &mut dyn Write, x: i32) -> io::Result<()> {
+    writeln!(out, "x is {x}")?;
+    Ok(())
 }
 
-fn multiple_parameters(value: i32, c: char) {
-    println!("first parameter: {value} , second parameter: {c}");
+fn multiple_parameters(out: &mut dyn Write, value: i32, c: char) -> io::Result<()> {
+    writeln!(out, "first parameter: {value} , second parameter: {c}")?;
+    Ok(())
 }
 
 fn func_returning_value(i: i32) -> i32{
@@ -132,31 +176,34 @@ fn statements_and_expression() {
     };            
 }
 
-fn control_flow() {
-    println!("=========Inside Control Flow=========");
+fn control_flow(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=========Inside Control Flow=========")?;
     let number = 6;
+    // The same chain, written as data: see crate::rules.
     if number % 4 == 0 {
-        println!("number is divisible by 4");
+        writeln!(out, "number is divisible by 4")?;
     } else if number % 3 == 0 {
-        println!("number is divisible by 3");
+        writeln!(out, "number is divisible by 3")?;
     } else {
-        println!("number is not divisible by 4, 3");
+        writeln!(out, "number is not divisible by 4, 3")?;
     }
     
     // if an let
     // both arm must have same return type
     let number = if 2>1 {5} else {6}; 
-    println!("value is {number}");
+    writeln!(out, "value is {number}")?;
     
     // loop
     let mut x = 1;
     loop {
-        println!("you are in loop");
+        writeln!(out, "you are in loop")?;
         if x == 3 {break}
         x += 1;
     }
     
     // returning values from loops
+    // (crate::sequences turns loops like these into iterators, e.g. the
+    // Fibonacci numbers, as far as a u64 goes or with no limit at all)
     let mut counter = 0;
     let result = loop{
       counter += 1;  
@@ -164,16 +211,16 @@ fn control_flow() {
           break counter * 2;
       }
     };
-    println!("result is {result}"); // 10
+    writeln!(out, "result is {result}")?; // 10
     
     // Loop labels to Disabmbiguate between multiple loops
     let mut count = 0;
     'counting_up: loop {
-        println!("count = {count}");
+        writeln!(out, "count = {count}")?;
         let mut remaining = 10;
 
         loop {
-            println!("remaining = {remaining}");
+            writeln!(out, "remaining = {remaining}")?;
             if remaining == 9 {
                 break;
             }
@@ -184,40 +231,41 @@ fn control_flow() {
         }
         count += 1;
     }
-    println!("end count {count}"); // 2
+    writeln!(out, "end count {count}")?; // 2
     
     // while loop
     let mut number = 3;
     while number !=0 {
-        println!("{number}");
+        writeln!(out, "{number}")?;
         number -= 1;
     }
-    println!("while its done :)");
+    writeln!(out, "while its done :)")?;
     
     // loop throuhg an array
     let arr = [10,20,30,40,50];
     let mut i = 0;
     while i < 5 {
-        println!("value is {}", arr[i]);
+        writeln!(out, "value is {}", arr[i])?;
         i += 1;
     }
     
     // more safer way to loop through array
-    println!("===safe loop through array===");
+    writeln!(out, "===safe loop through array===")?;
     let arr = [10,20,30,40,50];
     for element in arr {
-        println!("value is {element}");
+        writeln!(out, "value is {element}")?;
     }
     
     // range based for loop
-    println!("==range based for loop==");
+    writeln!(out, "==range based for loop==")?;
     for number in (1..4).rev() {
-        print!("{number}! ");
+        write!(out, "{number}! ")?;
     }
-    println!();
+    writeln!(out)?;
     // start and end inclusive
     for number in (1..=4).rev() {
-        print!("{number}! "); // 4 3 2 1
+        write!(out, "{number}! ")?; // 4 3 2 1
     }
-    println!();
+    writeln!(out)?;
+    Ok(())
 }
\ No newline at end of file
@@ -2,7 +2,52 @@
 // Ownership
 ////////////////////////////
 
-pub fn run() {
+use std::io::{self, Write};
+
+use crate::alloc_trace::Timeline;
+use crate::chapter::Chapter;
+use crate::traced::{Log, Traced};
+
+pub struct Part04;
+
+impl Chapter for Part04 {
+    fn id(&self) -> &str {
+        "04"
+    }
+
+    fn title(&self) -> &str {
+        "Ownership"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["stack and heap", "move", "clone", "drop"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+
+    // The move and clone from `strings_and_heap` get a step each, so that
+    // `hello-rust trace 04` shows the move allocating nothing and the clone
+    // allocating once.
+    fn trace(&self, timeline: &mut Timeline) -> io::Result<()> {
+        timeline.step("variable scope", variable_scope);
+        let s1 = timeline.step("let s1 = String::from(\"Hello\")", || String::from("Hello"));
+        let s2 = timeline.step("let s2 = s1", move || s1);
+        let s3 = timeline.step("let s3 = s2.clone()", || s2.clone());
+        timeline.step("drop(s2); drop(s3)", move || {
+            drop(s2);
+            drop(s3);
+        });
+        timeline.step("strings and heap", || strings_and_heap(&mut io::sink()))?;
+        timeline.step("ownership and functions", || {
+            ownership_and_functions(&mut io::sink())
+        })?;
+        Ok(())
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     // Ownership is a set of rules that governs how a Rust program manages memory.
     
     // Stack and Heap
@@ -32,8 +77,10 @@ pub fn run() {
     // 3. When the owner goes out of scope, the value will be dropped.
     
     variable_scope();    
-    strings_and_heap();
-    ownership_and_functions();
+    strings_and_heap(out)?;
+    ownership_and_functions(out)?;
+    return_values_and_scope(out)?;
+    Ok(())
 }
 
 fn variable_scope() {
@@ -43,7 +90,7 @@ fn variable_scope() {
     } // evening_message is no longer valid, its scope is over.    
 }
 
-fn strings_and_heap() {
+fn strings_and_heap(out: &mut dyn Write) -> io::Result<()> {
     let str_literal = "a_string_literal";
     // String literal is a value hardcoded into your program.
     // String literals are convenient but they are not suitable for every situation in which
@@ -59,7 +106,7 @@ fn strings_and_heap() {
     // mutable string
     let mut b_message = String::from("Hello");
     b_message.push_str(", World!");
-    println!("{}", b_message);
+    writeln!(out, "{}", b_message)?;
     
     // In order to support a mutable, growable piece of text, we need to allocate an amount of memory
     // on the heap, unknown at compile time, to hold the contents. This means:
@@ -85,6 +132,8 @@ fn strings_and_heap() {
     
     // s1 is reference to a memory location in heap, which container 'Hello'.
     // `let s2=s1` cause reference to be copied into s2 instead of heap content.
+    // `cargo run --features alloc-trace -- trace 04` counts it: no allocation
+    // for the move, one for the clone below.
     
     // We know that when a variable goes out of scopeRust automatically calls the `drop` function.
     // But s1 and s2 both refers to same memory location. This is a problem: when s2 and s1 go out 
@@ -96,13 +145,21 @@ fn strings_and_heap() {
     // This can be prooved by the fact that, s1 is not acceble once you assigned it to s2.
     
     // println!("{}", s1); Error: borrow of moved value
+
+    // A traced string shows it: the move is recorded, and only s2 drops it.
+    let log = Log::new();
+    {
+        let s1 = log.track("s1", String::from("Hello"));
+        let s2 = s1.hand_to("s2");
+    }
+    write_events(out, &log)?;
     
     //////////////////////////////////////////
     // Ways Variables and Data Interact: Clone
     //////////////////////////////////////////
     let s1 = String::from("Hello");
     let s2 = s1.clone();
-    println!("s1={}, s2={}", s1,s2);
+    writeln!(out, "s1={}, s2={}", s1,s2)?;
     // This works just fine, s1 and s2 both are accessbile since 
     // we copied the heap data and assigned its pointer to s2.
     
@@ -114,42 +171,61 @@ fn strings_and_heap() {
     // but rather are trivially copied, making them still valid after assignment to another variable.
     
     // Rust won’t let us annotate a type with Copy if the type, or any of its parts, has implemented 
-    // the Drop trait.    
+    // the Drop trait.
+    Ok(())
 }
 
-fn ownership_and_functions() {
+fn ownership_and_functions(out: &mut dyn Write) -> io::Result<()> {
     // The mecahnics of passing a value to a function are similar to those when assigning 
     // a value to a variable.
     
-    let message = String::from("Hello");
-    takes_ownership(message); // message value moved into the function and so
-                            // no longer valid here
+    // The strings are traced (see crate::traced) to show where they are dropped.
+    let log = Log::new();
+    let message = log.track("message", String::from("Hello"));
+    // message value moved into the function and so no longer valid here
+    takes_ownership(out, message.hand_to("takes_ownership"))?;
 
     let x = 54;
-    makes_copy(x); // a copy of x is passed to the function.
+    makes_copy(out, x)?; // a copy of x is passed to the function.
+    write_events(out, &log)
 }
 
-fn takes_ownership(message: String) {
-    println!("{}", message);
-}
+pub fn takes_ownership(out: &mut dyn Write, message: Traced<String>) -> io::Result<()> {
+    writeln!(out, "{}", message)?;
+    Ok(())
+} // message goes out of scope and is dropped here, before the caller carries on.
 
-fn makes_copy(n: i32) {
-    println!("{}", n);
+fn makes_copy(out: &mut dyn Write, n: i32) -> io::Result<()> {
+    writeln!(out, "{}", n)?;
+    Ok(())
 }
 
-fn return_values_and_scope() {
+fn return_values_and_scope(out: &mut dyn Write) -> io::Result<()> {
     // Returning values can also transfer ownership.
-    let s1 = gives_ownership();   
-    let s2 = String::from("Hello");    
-    let s3 = takes_and_gives_back(s2);
+    let log = Log::new();
+    {
+        let s1 = gives_ownership(&log).hand_to("s1");
+        let s2 = log.track("s2", String::from("Hello"));
+        let s3 = takes_and_gives_back(s2.hand_to("takes_and_gives_back")).hand_to("s3");
+    } // s3 and s1 are dropped, in reverse order of declaration. s2 was moved, so nothing
+      // happens to it.
+    write_events(out, &log)
+}
+
+pub fn gives_ownership(log: &Log) -> Traced<String> {
+    let some_string = log.track("gives_ownership", String::from("yours"));
+    some_string.hand_to("the caller") // returned and moved out to the calling function
 }
 
-fn gives_ownership() -> String {
-    String::from("yours")
+pub fn takes_and_gives_back(s: Traced<String>) -> Traced<String> {
+    s.hand_to("the caller") // returned and moves out to the calling function
 }
 
-fn takes_and_gives_back(s: String) -> String {
-    s
+fn write_events(out: &mut dyn Write, log: &Log) -> io::Result<()> {
+    for event in log.events() {
+        writeln!(out, "{}", event)?;
+    }
+    Ok(())
 }
 
 // While this works, taking ownership and then returning ownership with every function is a bit tedious.
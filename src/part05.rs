@@ -2,20 +2,46 @@
 // Referencing and Borrowing
 //////////////////////////////////
 
-pub fn run() {
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part05;
+
+impl Chapter for Part05 {
+    fn id(&self) -> &str {
+        "05"
+    }
+
+    fn title(&self) -> &str {
+        "Referencing and Borrowing"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["references", "mutable references", "slices"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     // A reference is like a pointer in that it's an address we can follow to access 
     // the data stored at that address; that data is owned by some other variable.
     
-    passing_ref_to_fn();
-    mutable_references();      
-    reference_restrictions();
-    slice_type();
+    passing_ref_to_fn(out)?;
+    mutable_references(out)?;      
+    reference_restrictions(out)?;
+    slice_type(out)?;
+    Ok(())
 }
 
-fn passing_ref_to_fn() {
+fn passing_ref_to_fn(out: &mut dyn Write) -> io::Result<()> {
     let s1 = String::from("Hello");
     let lenght = calculate_length(&s1); // ref passing or borrowing
-    println!("Lenght of '{}' is {}.", s1, lenght);
+    writeln!(out, "Lenght of '{}' is {}.", s1, lenght)?;
+    Ok(())
 }
 
 // The scope in which the variable s is valid is the same as any function parameter’s scope, 
@@ -27,10 +53,11 @@ fn calculate_length(s: &String) -> usize {
     s.len()
 }
 
-fn mutable_references() {
+fn mutable_references(out: &mut dyn Write) -> io::Result<()> {
     let mut s = String::from("Hello");
     change_string(&mut s); // passing mutable reference
-    println!("String after change: {}", s);
+    writeln!(out, "String after change: {}", s)?;
+    Ok(())
 }
 
 fn change_string(s: &mut String) {
@@ -39,12 +66,12 @@ fn change_string(s: &mut String) {
 
 // If you have mutable reference to a value, you can have no other references to that value.
 // The benefit of this restriction is that Rust can prevent data races at compile time.
-fn reference_restrictions() {
+fn reference_restrictions(out: &mut dyn Write) -> io::Result<()> {
     let mut s = String::from("Hello");
     let r1 = &mut s;    
     // ERROR `let r2 = &mut s;`    
     // cannot borrow `s` as mutable more than once at a time
-    println!("{}", r1);
+    writeln!(out, "{}", r1)?;
     
     // As always, you can use curly brackets to create a new scope, allowing for 
     // multiple mutable references, just not simulataneuos ones:
@@ -60,16 +87,17 @@ fn reference_restrictions() {
     let r2 = &s2;
     // ERROR: let r3 = &mut s2;
     // cannot borrow `s2` as mutable because it is also borrowed as immutable
-    println!("{}, {}", r1, r2);
+    writeln!(out, "{}, {}", r1, r2)?;
     
     // From code above it's also clear that multiple immutable references are allowed 
     // because no one has ability to change the data, and hence no race condition.
     
     let r3 = &mut s2;
-    println!("{}", r3);
+    writeln!(out, "{}", r3)?;
     // The scopes of the immutable references r1 and r2 end after the println! where they are last used, 
     // which is before the mutable reference r3 is created. These scopes don’t overlap, so this code is 
     // allowed.
+    Ok(())
 }
 
 
@@ -87,7 +115,7 @@ fn reference_restrictions() {
 //     &s
 // }
 
-fn slice_type() {
+fn slice_type(out: &mut dyn Write) -> io::Result<()> {
     // Slice lets you reference a contiguous sequence of elements in a collection rather than the whole
     // collection. A slice is a kind of reference, so it does not have ownership.
     let s = String::from("Hello World");
@@ -108,7 +136,7 @@ fn slice_type() {
     // write a function to get first word of a sentence
     let s = String::from("Goodbye World");
     let w = first_word(&s);
-    println!("{}", w);
+    writeln!(out, "{}", w)?;
     
     // This code will not compile
     let mut s2 = String::from("Make a hay while the sun shine");
@@ -120,10 +148,18 @@ fn slice_type() {
     // Other slices
     let a = [1, 2, 3, 4, 5];
     let slice = &a[1..3];
-    assert_eq!(slice, &[2, 3]);        
+    assert_eq!(slice, &[2, 3]);
+    Ok(())
 }
 
-fn first_word(s: &String) -> &str {
+/// Returns the first word of `s`, i.e. everything before the first space, or
+/// the whole string when it has no space.
+///
+/// ```
+/// let s = String::from("Goodbye World");
+/// assert_eq!(hello_rust::slices::first_word(&s), "Goodbye");
+/// ```
+pub fn first_word(s: &String) -> &str {
     let bytes = s.as_bytes();
     
     for(i, &item) in bytes.iter().enumerate() {
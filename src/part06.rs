@@ -2,6 +2,30 @@
 // Structs to Structure Related Data
 ////////////////////////////////////////
 
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part06;
+
+impl Chapter for Part06 {
+    fn id(&self) -> &str {
+        "06"
+    }
+
+    fn title(&self) -> &str {
+        "Structs to Structure Related Data"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["structs", "tuple structs", "methods", "associated functions"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
 // A structure is a custom data type.
 struct SeaCreature { 
     animal_type: String,
@@ -17,7 +41,7 @@ struct Location(i32, i32);
 // Sturcts do not have to have any fields at all.
 struct Marker;
 
-pub fn run() {
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     // When we instantiate a struct in our code our program creates the associated field data side by side in memory.
     let ferris = SeaCreature {
         animal_type: String::from("Crab"),
@@ -27,10 +51,11 @@ pub fn run() {
         weapon: String::from("claw"),            
     };
     
-    println!(
+    writeln!(
+        out,
         "{} is a {}. They have {} arms, {} legs, and a {} weapon",
         ferris.name, ferris.animal_type, ferris.arms, ferris.legs, ferris.weapon
-    );
+    )?;
  
     // We used the owned String type rather than the &str string slice type. This is a deliberate choice because
     // we want each instance of this struct to own all of its data and for that data to be valid for as long as 
@@ -41,7 +66,7 @@ pub fn run() {
     
     // Tuple struct
     let loc = Location(42, 32);
-    println!("{}, {}", loc.0, loc.1);
+    writeln!(out, "{}, {}", loc.0, loc.1)?;
 
     // Unit struct
     let m = Marker;
@@ -51,15 +76,15 @@ pub fn run() {
         height: 50,
     };
     
-    println!("Rectangle is : {:?}", rect1);
-    println!("Area of rectangle is {}", area(&rect1));
+    writeln!(out, "Rectangle is : {:?}", rect1)?;
+    writeln!(out, "Area of rectangle is {}", area(&rect1))?;
     
     // Method syntax
     let rect2 = Rectangle {
         width: 25,
         height: 30,
     };
-    println!("Area of rectangle is {}", rect2.area());
+    writeln!(out, "Area of rectangle is {}", rect2.area())?;
     
     // Methods with more parameters
     let rect1 = Rectangle { width: 30, height: 50, };
@@ -67,17 +92,19 @@ pub fn run() {
     let rect3 = Rectangle { width: 60, height: 45, };
     
     // write a method can_hold() to compare two rectangles
-    println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
-    println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+    writeln!(out, "Can rect1 hold rect2? {}", rect1.can_hold(&rect2))?;
+    writeln!(out, "Can rect1 hold rect3? {}", rect1.can_hold(&rect3))?;
     
     // Associated function calling
     let square = Rectangle::square(3);
+    Ok(())
 }
 
+/// A rectangle with whole-number sides.
 #[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
 }
 
 fn area(rectangle: &Rectangle) -> u32 {
@@ -95,7 +122,8 @@ impl Rectangle {
     // for their first parameter, so Rust lets you abbreviate this with only the name self in the first
     // parameter spot. Note that we still need to use the & in front of the self shorthand to indicate 
     // this method borrows the Self instance.
-    fn area(&self) -> u32 {
+    /// Width times height.
+    pub fn area(&self) -> u32 {
         self.width * self.height
     }
     
@@ -103,11 +131,22 @@ impl Rectangle {
     // The return value of can_hold will be a Boolean, and the implementation will
     // check whether the width and height of self are both greater than the width 
     // and height of the other Rectangle.
-    fn can_hold(&self, other: &Rectangle) -> bool {
+    /// Whether `other` fits strictly inside this rectangle without rotating it.
+    ///
+    /// ```
+    /// use hello_rust::structs::Rectangle;
+    ///
+    /// let big = Rectangle { width: 30, height: 50 };
+    /// let small = Rectangle { width: 10, height: 40 };
+    /// assert!(big.can_hold(&small));
+    /// assert!(!small.can_hold(&big));
+    /// ```
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
         self.width > other.width && self.height > other.height
     }
     
-    fn square(size: u32) -> Self {
+    /// A rectangle whose width and height are both `size`.
+    pub fn square(size: u32) -> Self {
         Self {
             width: size,
             height: size,
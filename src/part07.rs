@@ -2,17 +2,42 @@
 // Enums and Pattern Matching
 //////////////////////////////////
 
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part07;
+
+impl Chapter for Part07 {
+    fn id(&self) -> &str {
+        "07"
+    }
+
+    fn title(&self) -> &str {
+        "Enums and Pattern Matching"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["enums", "match", "Option", "if let"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
 // Enumeration allow you to create a new type that can havea value of several
 // tagged elements using the enum keyword.
 
-enum Species {
+// pub(crate) so that crate::layout can report their sizes.
+pub(crate) enum Species {
     Crab,
     Octopus,
     Fish,
     Clam,
 }
 
-struct SeaCreature {
+pub(crate) struct SeaCreature {
     species: Species,
     name: String,
     arms: i32,
@@ -20,7 +45,7 @@ struct SeaCreature {
     weapon : String,
 }
 
-pub fn run() {
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     let ferris = SeaCreature {
         species: Species::Crab,
         name: String::from("Ferris"),
@@ -32,10 +57,10 @@ pub fn run() {
     // match helps ensure exhaustive handling of all possible enum values making
     // it a powerful tool in ensuring quality code.
     match ferris.species {
-        Species::Crab => println!("{} is a crab", ferris.name),
-        Species::Octopus => println!("{} is a octopus", ferris.name),
-        Species::Fish => println!("{} is a fish", ferris.name),
-        Species::Clam => println!("{} is a clam", ferris.name),
+        Species::Crab => writeln!(out, "{} is a crab", ferris.name)?,
+        Species::Octopus => writeln!(out, "{} is a octopus", ferris.name)?,
+        Species::Fish => writeln!(out, "{} is a fish", ferris.name)?,
+        Species::Clam => writeln!(out, "{} is a clam", ferris.name)?,
     }
 
     ////////////////////////
@@ -46,24 +71,25 @@ pub fn run() {
 
     // An enum data value will have a memory size equal to its largest elements.
     // In addition to element data types, each element also has a numeric value that represents which tag it is.
+    // (Often the tag takes no room of its own: `hello-rust layout` shows when.)
 
     // Rust' enum is also known as tagget union.
     // The combining of types to make a new type is why we say Rust has algebraic types.
-    enum_with_data();
+    enum_with_data(out)?;
 
     // The Option<T> enum is so useful that it’s even included in the prelude; you
     // don’t need to bring it into scope explicitly. Its variants are also included
     // in the prelude: you can use Some and None directly without the Option:: prefix
     // The Option<T> enum is still just a regular enum, and Some(T) and None are still
     // variants of type Option<T>.
-    optional_value();
+    optional_value(out)?;
 
     // if-let
     // For some use cases, `match` is awkward. E.g
     let optional = Some(7);
     match optional {
         Some(i) => {
-            println!("Optional value is {:?}", i);
+            writeln!(out, "Optional value is {:?}", i)?;
         }
         _ => {},
     };
@@ -77,39 +103,39 @@ pub fn run() {
     // The `if-let` construct reads: if `let` destructures `number` into `Some(i)`
     // evaluate the block, else do nothing.
     if let Some(i) = number {
-        println!("Matched {}", i);
+        writeln!(out, "Matched {}", i)?;
     }
 
     // If you need to specify a failure, use an else:
     if let Some(i) = letter {
-        println!("Matched {}", i);
+        writeln!(out, "Matched {}", i)?;
     } else {
         // Destructuring failed, change the failure case.
-        println!("Didn't match a number.");
+        writeln!(out, "Didn't match a number.")?;
     }
 
     // Provide an altered failing condition
     let i_like_letters = false;
 
     if let Some(i) = emoticon {
-        println!("Matched {}", i);
+        writeln!(out, "Matched {}", i)?;
     } else if i_like_letters {
-        println!("Didn't match a number");
+        writeln!(out, "Didn't match a number")?;
     } else {
-        println!("I don't like letters.");
+        writeln!(out, "I don't like letters.")?;
     }
-
+    Ok(())
 }
 
-enum PoisonType { Acidic, Painful, Lethal }
-enum Size { Big, Small }
-enum Weapon {
+pub(crate) enum PoisonType { Acidic, Painful, Lethal }
+pub(crate) enum Size { Big, Small }
+pub(crate) enum Weapon {
     Claw(i32, Size),
     Poison(PoisonType),
     None
 }
 
-struct SeaCreaturesV2 {
+pub(crate) struct SeaCreaturesV2 {
     species: Species,
     name: String,
     arms: i32,
@@ -117,7 +143,7 @@ struct SeaCreaturesV2 {
     weapon: Weapon,
 }
 
-fn enum_with_data() {
+fn enum_with_data(out: &mut dyn Write) -> io::Result<()> {
     let ferris = SeaCreaturesV2 {
         species: Species::Crab,
         name: String::from("Ferris"),
@@ -134,13 +160,14 @@ fn enum_with_data() {
                         Size::Big => "big",
                         Size::Small => "small"
                     };
-                    println!("ferris is a crab with {} {} claws", num_claws, size_description)
+                    writeln!(out, "ferris is a crab with {} {} claws", num_claws, size_description)?
                 },
-                _ => println!("ferris is a crab with some other weapon")
+                _ => writeln!(out, "ferris is a crab with some other weapon")?
             }
         },
-        _ => println!("ferris is some other animal"),
+        _ => writeln!(out, "ferris is some other animal")?,
     }
+    Ok(())
 }
 
 // The Option Enum
@@ -149,7 +176,7 @@ fn enum_with_data() {
 //      Some(T),
 //  }
 
-fn optional_value() {
+fn optional_value(out: &mut dyn Write) -> io::Result<()> {
     let a_number = Some(5);
     let a_char = Some('z');
     let absent_number: Option<i32> = None;
@@ -171,7 +198,7 @@ fn optional_value() {
     let y: Option<i8> = Some(5);
     let n = y.unwrap();
     let sum = x + n;
-    println!("Sum is {}", sum);
+    writeln!(out, "Sum is {}", sum)?;
 
     // Note:
     // Unwrapping a `Some` variant will extract the value wrapped.
@@ -181,12 +208,19 @@ fn optional_value() {
     let five = Some(5);
     let six = add_one(five);
     let none = add_one(None);
-    println!("Six: {}", six.unwrap());
+    writeln!(out, "Six: {}", six.unwrap())?;
     // ERRRO: pritnln!("None: {}", none.unwrap());
     // Cannot unwrap None
+    Ok(())
 }
 
-fn add_one(n: Option<i32>) -> Option<i32> {
+/// Adds one to the value inside `Some`, leaving `None` untouched.
+///
+/// ```
+/// assert_eq!(hello_rust::enums::add_one(Some(5)), Some(6));
+/// assert_eq!(hello_rust::enums::add_one(None), None);
+/// ```
+pub fn add_one(n: Option<i32>) -> Option<i32> {
     match n {
         None => None,
         Some(i) => Some(i+1),
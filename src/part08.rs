@@ -2,6 +2,30 @@
 // Managing Large Projects with Packages & Modules
 //////////////////////////////////////////////////
 
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part08;
+
+impl Chapter for Part08 {
+    fn id(&self) -> &str {
+        "08"
+    }
+
+    fn title(&self) -> &str {
+        "Managing Large Projects with Packages & Modules"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["packages", "crates", "modules"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
 // Reference: https://www.sheshbabu.com/posts/rust-module-system/
 // https://aloso.github.io/2021/03/28/module-system.html
 
@@ -44,6 +68,10 @@ root.
 
 Cargo passes the crate root files to rustc to build the library or binary.
 
+This package has both: src/lib.rs is the root of the `hello_rust` library crate,
+which declares every chapter module, and src/main.rs is a small binary crate that
+only parses the command line and calls into the library through `hello_rust::` paths.
+
 */
 
 /*
@@ -113,6 +141,7 @@ Cargo passes the crate root files to rustc to build the library or binary.
 
 */
 
-pub fn run() {
-    println!("Read the commens in the file. No code");
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Read the comments in the file. No code")?;
+    Ok(())
 }
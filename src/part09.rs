@@ -2,6 +2,30 @@
 // Rust Collections
 /////////////////////////
 
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part09;
+
+impl Chapter for Part09 {
+    fn id(&self) -> &str {
+        "09"
+    }
+
+    fn title(&self) -> &str {
+        "Rust Collections"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["Vec", "String", "HashMap"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
 /*
    Rust’s standard library includes a number of very useful data structures called collections.
 
@@ -13,14 +37,15 @@
 
 */
 
-pub fn run() {
-    vectors();
-    enums();
-    strings();
-    hashmaps();
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    vectors(out)?;
+    enums(out)?;
+    strings(out)?;
+    hashmaps(out)?;
+    Ok(())
 }
 
-fn vectors() {
+fn vectors(out: &mut dyn Write) -> io::Result<()> {
     // creating a new empty vector
     let v: Vec<i32> = Vec::new();
     // Type annotation is require as our vector is empty and Rust cannot deduce the type.
@@ -36,13 +61,13 @@ fn vectors() {
 
     // Reading elemenst of vector
     let second: &i32 = &v[1]; // indexing starts with 0
-    println!("second: {}", second);
+    writeln!(out, "second: {}", second)?;
 
     // another way to read vector elements
     let third: Option<&i32> = v.get(2);
     match third {
-        Some(n) => println!("third: {}", n),
-        None => println!("no thrid elements"),
+        Some(n) => writeln!(out, "third: {}", n)?,
+        None => writeln!(out, "no thrid elements")?,
     }
 
     // This is not allowed
@@ -54,7 +79,7 @@ fn vectors() {
     // Iterating over a vector
     let v = vec![3, 5, 7, 11];
     for e in v {
-        println!("{}", e); // e is immutable
+        writeln!(out, "{}", e)?; // e is immutable
     }
 
     // Iterate using mutable reference
@@ -62,14 +87,16 @@ fn vectors() {
     for e in &mut v {
         *e += 10;
     }
-    println!("vector: {:?}", v); // [13, 15, 17, 21]
+    writeln!(out, "vector: {:?}", v)?; // [13, 15, 17, 21]
+    Ok(())
 }
 
 // Enums
 // Vectors can only store valuse that are the same type.
 // To store values of different type enum/struct is required.
 
-enum WebEvent {
+// pub(crate) so that crate::layout can report its size.
+pub(crate) enum WebEvent {
     PageLoad,
     PageUnload,
     KeyPress(char),
@@ -79,19 +106,20 @@ enum WebEvent {
 
 // A function which takes a `WebEvent` enum as an argument and
 // returns nothing.
-fn inspect(event: WebEvent) {
+fn inspect(out: &mut dyn Write, event: WebEvent) -> io::Result<()> {
     match event {
-        WebEvent::PageLoad => println!("page loaded"),
-        WebEvent::PageUnload => println!("page unloaded"),
-        WebEvent::KeyPress(c) => println!("pressed '{}'.", c),
-        WebEvent::Paste(s) => println!("pasted \"{}\".", s),
+        WebEvent::PageLoad => writeln!(out, "page loaded")?,
+        WebEvent::PageUnload => writeln!(out, "page unloaded")?,
+        WebEvent::KeyPress(c) => writeln!(out, "pressed '{}'.", c)?,
+        WebEvent::Paste(s) => writeln!(out, "pasted \"{}\".", s)?,
         WebEvent::Click { x, y } => {
-            println!("clicked at x={}, y={}.", x, y);
+            writeln!(out, "clicked at x={}, y={}.", x, y)?;
         },
     }
+    Ok(())
 }
 
-fn enums() {
+fn enums(out: &mut dyn Write) -> io::Result<()> {
     let pressed = WebEvent::KeyPress('x');
     // `to_owned()` creates an owned `String` from a string slice.
     let pasted  = WebEvent::Paste("my text".to_owned());
@@ -99,14 +127,15 @@ fn enums() {
     let load    = WebEvent::PageLoad;
     let unload  = WebEvent::PageUnload;
 
-    inspect(pressed);
-    inspect(pasted);
-    inspect(click);
-    inspect(load);
-    inspect(unload);
+    inspect(out, pressed)?;
+    inspect(out, pasted)?;
+    inspect(out, click)?;
+    inspect(out, load)?;
+    inspect(out, unload)?;
+    Ok(())
 }
 
-fn strings() {
+fn strings(out: &mut dyn Write) -> io::Result<()> {
     // Rust only has one type of string in the core language, which is string slice str.
     // The String type provided by Rust's standard library is growable, mutable, owned
     // UTF-8 encoded string type.
@@ -133,12 +162,12 @@ fn strings() {
     let mut s1 = String::from("foo");
     let s2 = "bar";
     s1.push_str(s2);
-    println!("s2: {}", s2); // owneeship is still with s2
+    writeln!(out, "s2: {}", s2)?; // owneeship is still with s2
 
     // add a single character
     let mut s = String::from("LO");
     s.push('L');
-    println!("{}", s); //LOL
+    writeln!(out, "{}", s)?; //LOL
 
     // String concatenation
     let s1 = String::from("Hello ");
@@ -192,19 +221,23 @@ fn strings() {
     // Iterating over Strings
     // For individual Unicode scalar values, use the chars method.
     for c in "Зд".chars() {
-        println!("{}", c);
+        writeln!(out, "{}", c)?;
     }
 
     // The byte method returns each raw byte
     for b in "Зд".bytes() {
-        println!("{}", b);
+        writeln!(out, "{}", b)?;
     }
-
+    Ok(())
 }
 
-fn hashmaps() {
+fn hashmaps(out: &mut dyn Write) -> io::Result<()> {
     // Creating a new hash map
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
+
+    // A HashMap iterates in an unspecified order that changes from run to run.
+    // To print the maps below in a stable order, their entries are first
+    // collected into a BTreeMap, which keeps its keys sorted.
 
     let mut scores = HashMap::new();
     scores.insert(String::from("Blue"), 10);
@@ -219,8 +252,8 @@ fn hashmaps() {
     // unwrap_or to set score to zero if scores doesn't have an entry for the key.
 
     // Iterate over each key/value pair
-    for (key, value) in &scores {
-        println!("{} \t {}", key, value);
+    for (key, value) in scores.iter().collect::<BTreeMap<_, _>>() {
+        writeln!(out, "{} \t {}", key, value)?;
     }
 
     // For types that implement the Copy trait, like i32, the values are copied
@@ -253,7 +286,7 @@ fn hashmaps() {
 
     scores.entry(String::from("Blue")).or_insert(50);
     scores.entry(String::from("Yellow")).or_insert(60);
-    println!("{:?}", scores); // {"Blue": 10, "Yellow": 60}
+    writeln!(out, "{:?}", scores.iter().collect::<BTreeMap<_, _>>())?; // {"Blue": 10, "Yellow": 60}
 
     // Updating a value based on the old value
     let text = "to be or not to be or be";
@@ -264,12 +297,11 @@ fn hashmaps() {
         *count += 1;
     }
 
-    println!("{:?}", word_count_map);
-    // {"to": 2, "not": 1, "be": 3, "or": 2}
+    writeln!(out, "{:?}", word_count_map.iter().collect::<BTreeMap<_, _>>())?;
+    // {"be": 3, "not": 1, "or": 2, "to": 2}
 
     // The split_whitespace method returns an iterator over sub-slices, separated
     // by whitespace, of the value in text. The or_insert method returns a mutable
     // reference (&mut V) to the value for the specified key.
-
-
+    Ok(())
 }
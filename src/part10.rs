@@ -1,3 +1,41 @@
+///////////////////////
+// Error Handling
+///////////////////////
+
+use std::io::{self, Write};
+
+use crate::alloc_trace::Timeline;
+use crate::chapter::Chapter;
+
+pub struct Part10;
+
+impl Chapter for Part10 {
+    fn id(&self) -> &str {
+        "10"
+    }
+
+    fn title(&self) -> &str {
+        "Error Handling"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["panic!", "Result", "? operator"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+
+    // The chapter panics when hello.txt is missing, which is its point; a
+    // trace run from another directory reports the missing file instead.
+    fn trace(&self, timeline: &mut Timeline) -> io::Result<()> {
+        if let Err(error) = std::fs::File::open("hello.txt") {
+            return Err(io::Error::new(error.kind(), format!("hello.txt: {}", error)));
+        }
+        timeline.step(self.title(), || self.run(&mut io::sink()))
+    }
+}
+
 // Rust groups errors into two major categories: recoverable and unrecoverable errors.
 // For a recoverable error, such as file not found, we may want to report the issue
 // to the user and retry. Unrecoverable errors are symptoms of bugs, like trying to
@@ -6,8 +44,9 @@
 // Rust does not have exceptions. Instead it has type Result<T, E> for recoverable errors,
 // and `panic!` for unrecoverable errors.
 
-pub fn run() {
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     recoverable_errors();
+    Ok(())
 }
 
 fn unrecoverable_errors() {
@@ -106,7 +145,7 @@ fn propagating_errors() ->  Result<String, io::Error> {
 }
 
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::Read;
 
 fn read_username_from_file() -> Result<String, io::Error> {
     let username_file_result = File::open("hello.txt");
@@ -144,7 +183,9 @@ fn read_username_from_file() -> Result<String, io::Error> {
 // the Err will be returned from the whole function as if we had used the return
 // keyword so the error value gets propagated to the calling code.
 
-fn read_username_from_file_v2() -> Result<String, io::Error> {
+/// Reads the username stored in `hello.txt` in the current directory,
+/// passing any I/O error on to the caller.
+pub fn read_username_from_file_v2() -> Result<String, io::Error> {
     let mut username_file = File::open("hello.txt")?;
     let mut username = String::new();
     username_file.read_to_string(&mut username)?;
@@ -164,7 +205,16 @@ fn read_username_from_file_v2() -> Result<String, io::Error> {
 // If the value is None, the None will be returned early from the function at that point.
 // If the value is Some, the value inside the Some is the resulting value of the
 // expression and the function continues
-fn last_char_of_first_line(text: &str) -> Option<char> {
+/// The last character of the first line of `text`, or `None` when `text` is
+/// empty or its first line is.
+///
+/// ```
+/// use hello_rust::errors::last_char_of_first_line;
+///
+/// assert_eq!(last_char_of_first_line("Hello, world\nHow are you"), Some('d'));
+/// assert_eq!(last_char_of_first_line(""), None);
+/// ```
+pub fn last_char_of_first_line(text: &str) -> Option<char> {
     text.lines().next()?.chars().last()
 }
 
@@ -1,6 +1,32 @@
+//////////////////////////////////////
 // Generic Types, Traits and Lifetimes
+//////////////////////////////////////
 
-pub fn run() {
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part11;
+
+impl Chapter for Part11 {
+    fn id(&self) -> &str {
+        "11"
+    }
+
+    fn title(&self) -> &str {
+        "Generic Types, Traits and Lifetimes"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["generic functions", "generic structs"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
 
     // Removing code duplication
     // Generics allow us to replace specific types with a placeholder that represents
@@ -9,7 +35,7 @@ pub fn run() {
     // Consider a function largest to find biggest element in a list
     let number_list = vec![34, 50, 25, 100, 65];
     let result = largest_i32(&number_list);
-    println!("largest i32 {}", result);
+    writeln!(out, "largest i32 {}", result)?;
 
     // Out largest function will work only for i32 types. What is we want to find
     // largest element from a list of chars.
@@ -17,18 +43,18 @@ pub fn run() {
     // We have to write another function, like this:
     let char_list = vec!['y', 'm', 'a', 'q'];
     let result = largest_char(&char_list);
-    println!("largest char {}", result);
+    writeln!(out, "largest char {}", result)?;
 
     // If we want one function which should work for i32 and char or any other type
     // which supports Ordering, we have to write a generic function with following
     // signature: fn largest<T>(list: &[T]) -> &T where T: std::cmp::PartialOrd
     let number_list = vec![34, 50, 25, 100, 65];
     let result = largest(&number_list);
-    println!("largest number is {}", result);
+    writeln!(out, "largest number is {}", result)?;
 
     let char_list = vec!['s', 'm', 'a', 'q'];
     let result = largest(&char_list);
-    println!("The largest char is {}", result);
+    writeln!(out, "The largest char is {}", result)?;
 
 
     // Generics in struct definition
@@ -38,9 +64,8 @@ pub fn run() {
     // Generic in method definition
     // We can implement methods on structs and enums and use generic types in their definitions
      let p = Point { x: 5, y: 10 };
-     println!("p.x = {}", p.x());
-
-
+     writeln!(out, "p.x = {}", p.x())?;
+    Ok(())
 }
 
 fn largest_i32(list: &[i32]) -> &i32 {
@@ -66,7 +91,15 @@ fn largest_char(list: &[char]) -> &char {
     largest
 }
 
-fn largest<T>(list: &[T]) -> &T
+/// The largest element of `list`.
+///
+/// Panics if `list` is empty.
+///
+/// ```
+/// assert_eq!(*hello_rust::generics::largest(&[34, 50, 25, 100, 65]), 100);
+/// assert_eq!(*hello_rust::generics::largest(&['y', 'm', 'a', 'q']), 'y');
+/// ```
+pub fn largest<T>(list: &[T]) -> &T
 where T: std::cmp::PartialOrd
 {
     let mut largest = &list[0];
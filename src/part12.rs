@@ -1,9 +1,36 @@
+////////////////////////////////////
 // Traits: Defining Shared Behaviour
+////////////////////////////////////
 
-pub fn run() {
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part12;
+
+impl Chapter for Part12 {
+    fn id(&self) -> &str {
+        "12"
+    }
+
+    fn title(&self) -> &str {
+        "Traits: Defining Shared Behaviour"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["traits", "trait bounds"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
+
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
     // A trait defines functionality a particular type has and can share with other types.
     // Lets define a trait for finding quadratic root of a number.
-    println!("{} {}", quadratic_root(100f64), quadratic_root(100f32));
+    writeln!(out, "{} {}", quadratic_root(100f64), quadratic_root(100f32))?;
+    Ok(())
 }
 
 // The meaning of the "HasSquareRoot" trait is that the "sq_root" function can be invoked on
@@ -1,9 +1,40 @@
+///////////////////////////////
 // Functional Language Features
+///////////////////////////////
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Part13;
+
+impl Chapter for Part13 {
+    fn id(&self) -> &str {
+        "13"
+    }
+
+    fn title(&self) -> &str {
+        "Functional Language Features"
+    }
+
+    fn topics(&self) -> &[&str] {
+        &["closures", "Fn", "FnMut", "FnOnce"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        run(out)
+    }
+}
 
 // Programming in a functional style often includes using functions as values by passing them in
 // arguments, returning them from other functions, assigning them to variables for later execution,
 // and so forth.
 
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Read the comments in the file. No code")?;
+    Ok(())
+}
+
 struct City {
     name: String,
     population: i64
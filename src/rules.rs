@@ -0,0 +1,325 @@
+//! Labels for numbers, decided by rules written as data: chapter 03's
+//! `if`/`else if` chain as a [`RuleSet`], which can also be read from a text
+//! file with one `condition: label` line per rule.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::numbers::{is_divisible, parse_range};
+
+/// Replaced by the number in a label.
+pub const NUMBER: &str = "{n}";
+
+/// What a rule checks about a number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// At least 1 in a [`RuleSet`], which refuses a divisor of 0.
+    DivisibleBy(u32),
+    InRange(RangeInclusive<u32>),
+    Even,
+    Odd,
+    /// The square of a whole number, like 0, 1, 4 or 9.
+    Square,
+}
+
+impl Condition {
+    pub fn holds(&self, number: u32) -> bool {
+        match self {
+            Condition::DivisibleBy(divisor) => is_divisible(number, *divisor),
+            Condition::InRange(range) => range.contains(&number),
+            Condition::Even => is_divisible(number, 2),
+            Condition::Odd => !is_divisible(number, 2),
+            Condition::Square => {
+                // The square root of a square that fits in a u32 is exact
+                // in an f64.
+                let root = f64::from(number).sqrt() as u32;
+                root * root == number
+            }
+        }
+    }
+}
+
+// Written the way the text format reads it back.
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Condition::DivisibleBy(divisor) => write!(f, "divisible by {}", divisor),
+            Condition::InRange(range) => write!(f, "in {}..={}", range.start(), range.end()),
+            Condition::Even => write!(f, "even"),
+            Condition::Odd => write!(f, "odd"),
+            Condition::Square => write!(f, "square"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseConditionError(String);
+
+impl fmt::Display for ParseConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown condition '{}', expected 'divisible by <n>' with n at least 1, \
+             'in <from>..=<to>', 'even', 'odd' or 'square'",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseConditionError {}
+
+// "divisible by 3" (or "div 3"), "in 1..=10", "even", "odd" or "square".
+impl FromStr for Condition {
+    type Err = ParseConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let condition = s.trim().to_lowercase();
+        let invalid = || ParseConditionError(s.trim().to_string());
+        match condition.as_str() {
+            "even" => return Ok(Condition::Even),
+            "odd" => return Ok(Condition::Odd),
+            "square" => return Ok(Condition::Square),
+            _ => {}
+        }
+        if let Some(range) = condition.strip_prefix("in ") {
+            return parse_range(range.trim())
+                .map(Condition::InRange)
+                .map_err(|_| invalid());
+        }
+        let divisor = condition
+            .strip_prefix("divisible by")
+            .or_else(|| condition.strip_prefix("div"))
+            .and_then(|divisor| divisor.trim().parse::<u32>().ok());
+        match divisor {
+            Some(divisor) if divisor >= 1 => Ok(Condition::DivisibleBy(divisor)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// How the labels of the rules that hold make up the result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// The first rule that holds wins, like an `if`/`else if` chain.
+    #[default]
+    Priority,
+    /// The labels of every rule that holds, in order, with nothing between.
+    Cumulative,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Priority => write!(f, "priority"),
+            Mode::Cumulative => write!(f, "cumulative"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseModeError(String);
+
+impl fmt::Display for ParseModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown mode '{}', expected priority or cumulative",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseModeError {}
+
+impl FromStr for Mode {
+    type Err = ParseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "priority" => Ok(Mode::Priority),
+            "cumulative" => Ok(Mode::Cumulative),
+            other => Err(ParseModeError(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub condition: Condition,
+    /// May contain [`NUMBER`].
+    pub label: String,
+}
+
+/// Rules in the order they are checked, with the label for numbers no rule
+/// holds for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+    mode: Mode,
+    otherwise: String,
+}
+
+impl RuleSet {
+    /// No rules yet; numbers are labelled with themselves.
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            rules: Vec::new(),
+            mode,
+            otherwise: NUMBER.to_string(),
+        }
+    }
+
+    /// A cumulative rule set with a label for each divisor, so that
+    /// `{3: Fizz, 5: Buzz}` plays FizzBuzz. Panics on a divisor of 0.
+    pub fn divisors<'a>(labels: impl IntoIterator<Item = (u32, &'a str)>) -> Self {
+        labels
+            .into_iter()
+            .fold(Self::new(Mode::Cumulative), |rules, (divisor, label)| {
+                rules.with_rule(Condition::DivisibleBy(divisor), label)
+            })
+    }
+
+    pub fn fizz_buzz() -> Self {
+        Self::divisors([(3, "Fizz"), (5, "Buzz")])
+    }
+
+    /// Adds a rule, checked after the ones already there. Panics on
+    /// `DivisibleBy(0)`, which no number is divisible by.
+    pub fn with_rule(mut self, condition: Condition, label: &str) -> Self {
+        assert!(
+            condition != Condition::DivisibleBy(0),
+            "a rule cannot check divisibility by 0"
+        );
+        self.rules.push(Rule {
+            condition,
+            label: label.to_string(),
+        });
+        self
+    }
+
+    pub fn with_otherwise(mut self, label: &str) -> Self {
+        self.otherwise = label.to_string();
+        self
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn otherwise(&self) -> &str {
+        &self.otherwise
+    }
+
+    /// The label for `number`.
+    pub fn apply(&self, number: u32) -> String {
+        let mut holding = self
+            .rules
+            .iter()
+            .filter(|rule| rule.condition.holds(number));
+        let label = match self.mode {
+            Mode::Priority => holding.next().map(|rule| rule.label.clone()),
+            Mode::Cumulative => {
+                let labels: String = holding.map(|rule| rule.label.as_str()).collect();
+                Some(labels).filter(|labels| !labels.is_empty())
+            }
+        };
+        label
+            .unwrap_or_else(|| self.otherwise.clone())
+            .replace(NUMBER, &number.to_string())
+    }
+
+    /// The label of every number in `range`, in order.
+    pub fn run(&self, range: RangeInclusive<u32>) -> impl Iterator<Item = String> + '_ {
+        range.map(|number| self.apply(number))
+    }
+
+    /// Reads a rule set from `text`: one `condition: label` line per rule,
+    /// plus optional `mode: priority|cumulative` and `otherwise: label`
+    /// lines. Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, RulesError> {
+        let mut rules = Self::new(Mode::default());
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = |reason: String| RulesError::Parse {
+                line: index + 1,
+                reason,
+            };
+            let (key, label) = line
+                .split_once(':')
+                .ok_or_else(|| parse_error("expected 'condition: label'".to_string()))?;
+            let label = label.trim();
+            match key.trim() {
+                "mode" => {
+                    rules.mode = label
+                        .parse()
+                        .map_err(|error: ParseModeError| parse_error(error.to_string()))?
+                }
+                "otherwise" => rules.otherwise = label.to_string(),
+                condition => {
+                    let condition = condition
+                        .parse()
+                        .map_err(|error: ParseConditionError| parse_error(error.to_string()))?;
+                    rules = rules.with_rule(condition, label);
+                }
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).map_err(|error| RulesError::Io(path.to_path_buf(), error))?;
+        Self::parse(&text)
+    }
+}
+
+/// FizzBuzz.
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::fizz_buzz()
+    }
+}
+
+// In the text format, so that what is shown can be saved and read back.
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "mode: {}", self.mode)?;
+        for rule in &self.rules {
+            writeln!(f, "{}: {}", rule.condition, rule.label)?;
+        }
+        write!(f, "otherwise: {}", self.otherwise)
+    }
+}
+
+#[derive(Debug)]
+pub enum RulesError {
+    Io(PathBuf, io::Error),
+    /// `line` counts from 1.
+    Parse {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RulesError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            RulesError::Parse { line, reason } => write!(f, "line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
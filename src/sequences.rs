@@ -0,0 +1,442 @@
+//! Fibonacci numbers, factorials, primes and Collatz sequences as iterators
+//! over any [`Natural`] type. Fixed-width terms stop before overflowing, and
+//! [`Terms::overflowed`] says so.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::bigint::BigUint;
+
+/// What the sequences need of a number type. Every operation is checked:
+/// `None` means the result does not fit.
+pub trait Natural: Clone + Ord + fmt::Display + From<u32> {
+    /// The name of the type, like `u64`.
+    const NAME: &'static str;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// `None` if `other` is zero.
+    fn checked_rem(&self, other: &Self) -> Option<Self>;
+
+    fn is_even(&self) -> bool;
+
+    /// Divided by two, rounded down.
+    fn half(&self) -> Self;
+}
+
+macro_rules! natural {
+    ($($ty:ty),*) => {$(
+        impl Natural for $ty {
+            const NAME: &'static str = stringify!($ty);
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$ty>::checked_add(*self, *other)
+            }
+
+            fn checked_mul(&self, other: &Self) -> Option<Self> {
+                <$ty>::checked_mul(*self, *other)
+            }
+
+            fn checked_rem(&self, other: &Self) -> Option<Self> {
+                <$ty>::checked_rem(*self, *other)
+            }
+
+            fn is_even(&self) -> bool {
+                self % 2 == 0
+            }
+
+            fn half(&self) -> Self {
+                self / 2
+            }
+        }
+    )*};
+}
+
+natural!(u32, u64, u128);
+
+/// Never overflows.
+impl Natural for BigUint {
+    const NAME: &'static str = "BigUint";
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_rem(&self, other: &Self) -> Option<Self> {
+        BigUint::checked_rem(self, other)
+    }
+
+    fn is_even(&self) -> bool {
+        BigUint::is_even(self)
+    }
+
+    fn half(&self) -> Self {
+        BigUint::half(self)
+    }
+}
+
+/// The terms of a sequence.
+pub trait Terms: Iterator {
+    /// Whether the terms run out because the next one does not fit in the
+    /// number type, rather than because the sequence ends.
+    fn overflowed(&self) -> bool;
+}
+
+/// 0, 1, 1, 2, 3, 5, 8, …: every term is the sum of the two before it.
+#[derive(Debug, Clone)]
+pub struct Fibonacci<N> {
+    current: Option<N>,
+    next: Option<N>,
+    overflowed: bool,
+}
+
+pub fn fibonacci<N: Natural>() -> Fibonacci<N> {
+    Fibonacci {
+        current: Some(N::from(0)),
+        next: Some(N::from(1)),
+        overflowed: false,
+    }
+}
+
+impl<N: Natural> Iterator for Fibonacci<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let term = self.current.take()?;
+        self.current = self.next.take();
+        if let Some(current) = &self.current {
+            self.next = term.checked_add(current);
+            self.overflowed = self.next.is_none();
+        }
+        Some(term)
+    }
+}
+
+impl<N: Natural> Terms for Fibonacci<N> {
+    fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+/// 0!, 1!, 2!, …: 1, 1, 2, 6, 24, …
+#[derive(Debug, Clone)]
+pub struct Factorials<N> {
+    current: Option<N>,
+    // The number whose factorial `current` is.
+    n: u32,
+    overflowed: bool,
+}
+
+pub fn factorials<N: Natural>() -> Factorials<N> {
+    Factorials {
+        current: Some(N::from(1)),
+        n: 0,
+        overflowed: false,
+    }
+}
+
+impl<N: Natural> Iterator for Factorials<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let term = self.current.take()?;
+        self.n += 1;
+        self.current = term.checked_mul(&N::from(self.n));
+        self.overflowed = self.current.is_none();
+        Some(term)
+    }
+}
+
+impl<N: Natural> Terms for Factorials<N> {
+    fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+/// 2, 3, 5, 7, 11, …, found by trial division by the primes found so far.
+#[derive(Debug, Clone)]
+pub struct Primes<N> {
+    found: Vec<N>,
+    candidate: Option<N>,
+    overflowed: bool,
+}
+
+pub fn primes<N: Natural>() -> Primes<N> {
+    Primes {
+        found: Vec::new(),
+        candidate: Some(N::from(2)),
+        overflowed: false,
+    }
+}
+
+impl<N: Natural> Primes<N> {
+    fn is_prime(&self, candidate: &N) -> bool {
+        for prime in &self.found {
+            // A square that does not fit is larger than any candidate.
+            match prime.checked_mul(prime) {
+                Some(square) if square <= *candidate => {}
+                _ => return true,
+            }
+            if candidate.checked_rem(prime) == Some(N::from(0)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<N: Natural> Iterator for Primes<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        loop {
+            let candidate = self.candidate.take()?;
+            self.candidate = candidate.checked_add(&N::from(1));
+            self.overflowed = self.candidate.is_none();
+            if self.is_prime(&candidate) {
+                self.found.push(candidate.clone());
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+impl<N: Natural> Terms for Primes<N> {
+    fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+/// From `start`, halve even numbers and turn odd ones into 3n + 1, until 1.
+/// Nobody has found a start that never gets there, nor proved there is none.
+/// Starting from 0, which would stay 0 forever, there are no terms.
+#[derive(Debug, Clone)]
+pub struct Collatz<N> {
+    current: Option<N>,
+    overflowed: bool,
+}
+
+pub fn collatz<N: Natural>(start: N) -> Collatz<N> {
+    Collatz {
+        current: Some(start).filter(|start| *start != N::from(0)),
+        overflowed: false,
+    }
+}
+
+impl<N: Natural> Iterator for Collatz<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let term = self.current.take()?;
+        if term == N::from(1) {
+            return Some(term);
+        }
+        self.current = if term.is_even() {
+            Some(term.half())
+        } else {
+            term.checked_mul(&N::from(3))
+                .and_then(|triple| triple.checked_add(&N::from(1)))
+        };
+        self.overflowed = self.current.is_none();
+        Some(term)
+    }
+}
+
+impl<N: Natural> Terms for Collatz<N> {
+    fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sequence {
+    Fibonacci,
+    Factorial,
+    Primes,
+    Collatz,
+}
+
+impl Sequence {
+    pub const ALL: [Sequence; 4] = [
+        Sequence::Fibonacci,
+        Sequence::Factorial,
+        Sequence::Primes,
+        Sequence::Collatz,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Sequence::Fibonacci => "fibonacci",
+            Sequence::Factorial => "factorial",
+            Sequence::Primes => "primes",
+            Sequence::Collatz => "collatz",
+        }
+    }
+
+    /// The index of the first term, as it is usually counted: F(0) = 0 and
+    /// 0! = 1, but the 1st prime is 2. A Collatz sequence starts at step 0.
+    pub fn first_index(self) -> u32 {
+        match self {
+            Sequence::Primes => 1,
+            _ => 0,
+        }
+    }
+
+    /// The terms of the sequence; `start` is only used by Collatz.
+    pub fn iter<N: Natural + 'static>(self, start: N) -> Box<dyn Terms<Item = N>> {
+        match self {
+            Sequence::Fibonacci => Box::new(fibonacci()),
+            Sequence::Factorial => Box::new(factorials()),
+            Sequence::Primes => Box::new(primes()),
+            Sequence::Collatz => Box::new(collatz(start)),
+        }
+    }
+
+    /// The terms with the given indexes. A Collatz sequence that ends
+    /// inside `indexes` gives the terms it has.
+    pub fn terms<N: Natural + 'static>(
+        self,
+        start: N,
+        indexes: RangeInclusive<u32>,
+    ) -> Result<Vec<N>, SequenceError> {
+        self.indexed(start, indexes).collect()
+    }
+
+    /// Like [`Sequence::terms`], one term at a time: the terms come as they
+    /// are worked out, followed by the error if there is one.
+    pub fn indexed<N: Natural + 'static>(
+        self,
+        start: N,
+        indexes: RangeInclusive<u32>,
+    ) -> Indexed<N> {
+        let first = self.first_index();
+        let error = (*indexes.start() < first).then_some(SequenceError::NoSuchIndex {
+            sequence: self,
+            index: *indexes.start(),
+        });
+        Indexed {
+            sequence: self,
+            terms: self.iter(start),
+            skip: indexes.start().saturating_sub(first) as usize,
+            first: *indexes.start(),
+            index: *indexes.start(),
+            end: *indexes.end(),
+            error,
+            done: false,
+        }
+    }
+}
+
+/// The terms of a sequence with the indexes asked for, made by
+/// [`Sequence::indexed`].
+pub struct Indexed<N> {
+    sequence: Sequence,
+    terms: Box<dyn Terms<Item = N>>,
+    // Terms before the first one wanted, still to be skipped.
+    skip: usize,
+    first: u32,
+    // The index of the next term, and of the last one wanted.
+    index: u32,
+    end: u32,
+    error: Option<SequenceError>,
+    done: bool,
+}
+
+impl<N: Natural> Iterator for Indexed<N> {
+    type Item = Result<N, SequenceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(error) = self.error.take() {
+            self.done = true;
+            return Some(Err(error));
+        }
+        let term = self.terms.nth(std::mem::take(&mut self.skip));
+        let Some(term) = term else {
+            self.done = true;
+            if self.terms.overflowed() {
+                return Some(Err(SequenceError::TooLarge {
+                    sequence: self.sequence,
+                    index: self.index,
+                    ty: N::NAME,
+                }));
+            }
+            // A Collatz sequence may end before the range does, but not
+            // before it starts.
+            return (self.index == self.first).then_some(Err(SequenceError::NoSuchIndex {
+                sequence: self.sequence,
+                index: self.first,
+            }));
+        };
+        if self.index == self.end {
+            self.done = true;
+        } else {
+            self.index += 1;
+        }
+        Some(Ok(term))
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = SequenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Sequence::ALL
+            .into_iter()
+            .find(|sequence| sequence.name() == s.trim().to_lowercase())
+            .ok_or_else(|| SequenceError::UnknownSequence(s.trim().to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    UnknownSequence(String),
+    /// Before the first term, or after the last term of a Collatz sequence.
+    NoSuchIndex {
+        sequence: Sequence,
+        index: u32,
+    },
+    /// The term at `index` does not fit in `ty`.
+    TooLarge {
+        sequence: Sequence,
+        index: u32,
+        ty: &'static str,
+    },
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceError::UnknownSequence(name) => write!(
+                f,
+                "unknown sequence '{}', expected fibonacci, factorial, primes or collatz",
+                name
+            ),
+            SequenceError::NoSuchIndex { sequence, index } => {
+                write!(f, "{} has no term {}", sequence, index)
+            }
+            SequenceError::TooLarge {
+                sequence,
+                index,
+                ty,
+            } => write!(f, "term {} of {} does not fit in a {}", index, sequence, ty),
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
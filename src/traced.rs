@@ -0,0 +1,189 @@
+//! Values that record being created, moved, cloned and dropped in a shared
+//! [`Log`], to watch chapter 04's ownership rules at work.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// Something that happened to a traced value, identified by its id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Created {
+        id: u32,
+        owner: &'static str,
+    },
+    Moved {
+        id: u32,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// `id` is the new value, a copy of `from`.
+    Cloned {
+        id: u32,
+        from: u32,
+        owner: &'static str,
+    },
+    Dropped {
+        id: u32,
+        owner: &'static str,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Created { id, owner } => write!(f, "#{} created, owned by {}", id, owner),
+            Event::Moved { id, from, to } => write!(f, "#{} moved from {} to {}", id, from, to),
+            Event::Cloned { id, from, owner } => {
+                write!(f, "#{} cloned from #{}, owned by {}", id, from, owner)
+            }
+            Event::Dropped { id, owner } => write!(f, "#{} dropped by {}", id, owner),
+        }
+    }
+}
+
+/// Where traced values record their events. Cloning a log gives another
+/// handle to the same events.
+#[derive(Debug, Clone, Default)]
+pub struct Log {
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl Log {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracing `value`, owned by `owner`. Ids count up from 1.
+    pub fn track<T>(&self, owner: &'static str, value: T) -> Traced<T> {
+        let id = self.next_id();
+        self.record(Event::Created { id, owner });
+        Traced {
+            id,
+            owner,
+            value,
+            log: self.clone(),
+        }
+    }
+
+    /// Everything recorded so far, oldest first.
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// The ids of the values dropped so far, in the order they were dropped.
+    pub fn drops(&self) -> Vec<u32> {
+        self.events
+            .borrow()
+            .iter()
+            .filter_map(|event| match event {
+                Event::Dropped { id, .. } => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn next_id(&self) -> u32 {
+        let events = self.events.borrow();
+        let values = events
+            .iter()
+            .filter(|event| matches!(event, Event::Created { .. } | Event::Cloned { .. }));
+        values.count() as u32 + 1
+    }
+
+    fn record(&self, event: Event) {
+        self.events.borrow_mut().push(event);
+    }
+}
+
+/// A value whose creation, moves, clones and drop are recorded in a [`Log`].
+pub struct Traced<T> {
+    id: u32,
+    owner: &'static str,
+    value: T,
+    log: Log,
+}
+
+impl<T> Traced<T> {
+    /// The number the value goes by in the log, unique within that log.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The variable or function the value belongs to, as last recorded.
+    pub fn owner(&self) -> &'static str {
+        self.owner
+    }
+
+    /// Moves the value to a new owner, recording the move: `let s2 =
+    /// s1.hand_to("s2")` is `let s2 = s1` with a trace.
+    pub fn hand_to(mut self, owner: &'static str) -> Self {
+        self.log.record(Event::Moved {
+            id: self.id,
+            from: self.owner,
+            to: owner,
+        });
+        self.owner = owner;
+        self
+    }
+
+    /// A copy of the value with an id of its own, owned by `owner`.
+    pub fn clone_as(&self, owner: &'static str) -> Self
+    where
+        T: Clone,
+    {
+        let id = self.log.next_id();
+        self.log.record(Event::Cloned {
+            id,
+            from: self.id,
+            owner,
+        });
+        Traced {
+            id,
+            owner,
+            value: self.value.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+/// Owned by the same owner; [`Traced::clone_as`] names a new one.
+impl<T: Clone> Clone for Traced<T> {
+    fn clone(&self) -> Self {
+        self.clone_as(self.owner)
+    }
+}
+
+impl<T> Deref for Traced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Drop for Traced<T> {
+    fn drop(&mut self) {
+        self.log.record(Event::Dropped {
+            id: self.id,
+            owner: self.owner,
+        });
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Traced")
+            .field("id", &self.id)
+            .field("owner", &self.owner)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
@@ -0,0 +1,446 @@
+//! Temperatures, lengths and times as newtypes around `f64`, so that only
+//! conversions that make sense compile. [`Quantity`] holds a value whose unit
+//! is only known at run time, like `98.6F` typed by a user.
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::duration::Span;
+
+macro_rules! quantity {
+    ($($(#[$doc:meta])* $name:ident, $symbol:expr;)*) => {$(
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f64);
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{} {}", format_number(self.0), $symbol)
+            }
+        }
+    )*};
+}
+
+quantity! {
+    Celsius, "°C";
+    Fahrenheit, "°F";
+    /// Counts from absolute zero, so it is never negative.
+    Kelvin, "K";
+    Metres, "m";
+    Feet, "ft";
+    Seconds, "s";
+}
+
+// Temperatures have no zero that means "none", so adding two of them makes
+// no sense; lengths and times do.
+macro_rules! additive {
+    ($($name:ident),*) => {$(
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                $name(self.0 - other.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = $name;
+
+            fn mul(self, factor: f64) -> $name {
+                $name(self.0 * factor)
+            }
+        }
+    )*};
+}
+
+additive!(Metres, Feet, Seconds);
+
+/// The lowest temperature there is.
+pub const ABSOLUTE_ZERO: Kelvin = Kelvin(0.0);
+
+/// 0 °C in kelvin.
+pub const ICE_POINT: f64 = 273.15;
+
+/// A foot is exactly this many metres.
+pub const METRES_PER_FOOT: f64 = 0.3048;
+
+impl From<Fahrenheit> for Celsius {
+    fn from(fahrenheit: Fahrenheit) -> Self {
+        Celsius((fahrenheit.0 - 32.0) * 5.0 / 9.0)
+    }
+}
+
+impl From<Kelvin> for Celsius {
+    fn from(kelvin: Kelvin) -> Self {
+        Celsius(kelvin.0 - ICE_POINT)
+    }
+}
+
+impl From<Celsius> for Fahrenheit {
+    fn from(celsius: Celsius) -> Self {
+        Fahrenheit(celsius.0 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+impl From<Kelvin> for Fahrenheit {
+    fn from(kelvin: Kelvin) -> Self {
+        Celsius::from(kelvin).into()
+    }
+}
+
+impl From<Celsius> for Kelvin {
+    fn from(celsius: Celsius) -> Self {
+        Kelvin(celsius.0 + ICE_POINT)
+    }
+}
+
+impl From<Fahrenheit> for Kelvin {
+    fn from(fahrenheit: Fahrenheit) -> Self {
+        Celsius::from(fahrenheit).into()
+    }
+}
+
+impl From<Feet> for Metres {
+    fn from(feet: Feet) -> Self {
+        Metres(feet.0 * METRES_PER_FOOT)
+    }
+}
+
+impl From<Metres> for Feet {
+    fn from(metres: Metres) -> Self {
+        Feet(metres.0 / METRES_PER_FOOT)
+    }
+}
+
+impl From<Span> for Seconds {
+    fn from(span: Span) -> Self {
+        // Exact up to 2^53 seconds, some 285 million years.
+        Seconds(span.as_secs() as f64)
+    }
+}
+
+impl From<Duration> for Seconds {
+    fn from(duration: Duration) -> Self {
+        Seconds(duration.as_secs_f64())
+    }
+}
+
+// Six significant digits, without trailing zeros: 37, 98.6, 16404.2, 0.00328084.
+fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return String::from("0");
+    }
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (5 - magnitude).clamp(0, 12) as usize;
+    let text = format!("{:.*}", decimals, value);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    if text == "-0" {
+        String::from("0")
+    } else {
+        text.to_string()
+    }
+}
+
+/// What a unit measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Temperature,
+    Length,
+    Time,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dimension::Temperature => write!(f, "a temperature"),
+            Dimension::Length => write!(f, "a length"),
+            Dimension::Time => write!(f, "a time"),
+        }
+    }
+}
+
+/// Every unit a [`Quantity`] can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Millimetres,
+    Centimetres,
+    Metres,
+    Kilometres,
+    Feet,
+    Miles,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 12] = [
+        Unit::Celsius,
+        Unit::Fahrenheit,
+        Unit::Kelvin,
+        Unit::Millimetres,
+        Unit::Centimetres,
+        Unit::Metres,
+        Unit::Kilometres,
+        Unit::Feet,
+        Unit::Miles,
+        Unit::Seconds,
+        Unit::Minutes,
+        Unit::Hours,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Celsius => "°C",
+            Unit::Fahrenheit => "°F",
+            Unit::Kelvin => "K",
+            Unit::Millimetres => "mm",
+            Unit::Centimetres => "cm",
+            Unit::Metres => "m",
+            Unit::Kilometres => "km",
+            Unit::Feet => "ft",
+            Unit::Miles => "mi",
+            Unit::Seconds => "s",
+            Unit::Minutes => "min",
+            Unit::Hours => "h",
+        }
+    }
+
+    // Other ways to write the unit, besides its symbol.
+    fn names(self) -> &'static [&'static str] {
+        match self {
+            Unit::Celsius => &["c", "celsius"],
+            Unit::Fahrenheit => &["f", "fahrenheit"],
+            Unit::Kelvin => &["kelvin"],
+            Unit::Millimetres => &["millimetres", "millimeters"],
+            Unit::Centimetres => &["centimetres", "centimeters"],
+            Unit::Metres => &["metre", "metres", "meter", "meters"],
+            Unit::Kilometres => &["kilometres", "kilometers"],
+            Unit::Feet => &["foot", "feet"],
+            Unit::Miles => &["mile", "miles"],
+            Unit::Seconds => &["sec", "second", "seconds"],
+            Unit::Minutes => &["minute", "minutes"],
+            Unit::Hours => &["hour", "hours"],
+        }
+    }
+
+    pub fn dimension(self) -> Dimension {
+        match self {
+            Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => Dimension::Temperature,
+            Unit::Seconds | Unit::Minutes | Unit::Hours => Dimension::Time,
+            _ => Dimension::Length,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+// The symbol or a name, in any case: "km", "°F", "F" or "feet".
+impl FromStr for Unit {
+    type Err = UnitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Unit::ALL
+            .into_iter()
+            .find(|unit| unit.symbol().to_lowercase() == name || unit.names().contains(&&*name))
+            .ok_or_else(|| UnitsError::UnknownUnit(s.trim().to_string()))
+    }
+}
+
+/// A number with a unit that is only known at run time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    /// Fails for infinities, NaN and temperatures below absolute zero.
+    pub fn new(value: f64, unit: Unit) -> Result<Self, UnitsError> {
+        if !value.is_finite() {
+            return Err(UnitsError::InvalidNumber(value.to_string()));
+        }
+        let quantity = Self { value, unit };
+        if unit.dimension() == Dimension::Temperature
+            && Kelvin::from(quantity.celsius()) < ABSOLUTE_ZERO
+        {
+            return Err(UnitsError::BelowAbsoluteZero(value, unit));
+        }
+        Ok(quantity)
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension()
+    }
+
+    /// The same amount in `unit`, which must measure the same thing.
+    pub fn to(&self, unit: Unit) -> Result<Quantity, UnitsError> {
+        if unit.dimension() != self.dimension() {
+            return Err(UnitsError::Mismatch {
+                from: self.dimension(),
+                to: unit.dimension(),
+            });
+        }
+        let value = match unit.dimension() {
+            Dimension::Temperature => {
+                let celsius = self.celsius();
+                match unit {
+                    Unit::Fahrenheit => Fahrenheit::from(celsius).0,
+                    Unit::Kelvin => Kelvin::from(celsius).0,
+                    _ => celsius.0,
+                }
+            }
+            Dimension::Length => {
+                let metres = self.metres();
+                match unit {
+                    Unit::Millimetres => metres.0 * 1_000.0,
+                    Unit::Centimetres => metres.0 * 100.0,
+                    Unit::Kilometres => metres.0 / 1_000.0,
+                    Unit::Feet => Feet::from(metres).0,
+                    Unit::Miles => Feet::from(metres).0 / 5_280.0,
+                    _ => metres.0,
+                }
+            }
+            Dimension::Time => {
+                let seconds = self.seconds();
+                match unit {
+                    Unit::Minutes => seconds.0 / 60.0,
+                    Unit::Hours => seconds.0 / 3_600.0,
+                    _ => seconds.0,
+                }
+            }
+        };
+        Ok(Quantity { value, unit })
+    }
+
+    /// The quantity in every unit of its dimension, its own first.
+    pub fn conversions(&self) -> Vec<Quantity> {
+        let others = Unit::ALL
+            .into_iter()
+            .filter(|unit| *unit != self.unit && unit.dimension() == self.dimension());
+        std::iter::once(*self)
+            .chain(others.map(|unit| self.to(unit).expect("the same dimension")))
+            .collect()
+    }
+
+    // Only called for temperatures, lengths and times respectively.
+    fn celsius(&self) -> Celsius {
+        match self.unit {
+            Unit::Fahrenheit => Fahrenheit(self.value).into(),
+            Unit::Kelvin => Kelvin(self.value).into(),
+            _ => Celsius(self.value),
+        }
+    }
+
+    fn metres(&self) -> Metres {
+        match self.unit {
+            Unit::Millimetres => Metres(self.value / 1_000.0),
+            Unit::Centimetres => Metres(self.value / 100.0),
+            Unit::Kilometres => Metres(self.value * 1_000.0),
+            Unit::Feet => Feet(self.value).into(),
+            Unit::Miles => Feet(self.value * 5_280.0).into(),
+            _ => Metres(self.value),
+        }
+    }
+
+    fn seconds(&self) -> Seconds {
+        match self.unit {
+            Unit::Minutes => Seconds(self.value * 60.0),
+            Unit::Hours => Seconds(self.value * 3_600.0),
+            _ => Seconds(self.value),
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", format_number(self.value), self.unit)
+    }
+}
+
+// A number and a unit, with or without a space: "98.6F", "5 km", "-40 °C".
+impl FromStr for Quantity {
+    type Err = UnitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        if unit.trim().is_empty() {
+            return Err(UnitsError::MissingUnit(text.to_string()));
+        }
+        let value = number
+            .parse()
+            .ok()
+            .filter(|value: &f64| value.is_finite())
+            .ok_or_else(|| UnitsError::InvalidNumber(number.to_string()))?;
+        Quantity::new(value, unit.parse()?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitsError {
+    InvalidNumber(String),
+    MissingUnit(String),
+    UnknownUnit(String),
+    BelowAbsoluteZero(f64, Unit),
+    Mismatch { from: Dimension, to: Dimension },
+}
+
+impl fmt::Display for UnitsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnitsError::InvalidNumber(number) => write!(f, "'{}' is not a number", number),
+            UnitsError::MissingUnit(text) => {
+                write!(f, "'{}' has no unit, write one like 98.6F or 5 km", text)
+            }
+            UnitsError::UnknownUnit(unit) => {
+                let symbols: Vec<&str> = Unit::ALL.iter().map(|unit| unit.symbol()).collect();
+                write!(
+                    f,
+                    "unknown unit '{}', expected one of {}",
+                    unit,
+                    symbols.join(", ")
+                )
+            }
+            UnitsError::BelowAbsoluteZero(value, unit) => write!(
+                f,
+                "{} {} is below absolute zero",
+                format_number(*value),
+                unit
+            ),
+            UnitsError::Mismatch { from, to } => {
+                write!(f, "cannot convert {} into {}", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnitsError {}
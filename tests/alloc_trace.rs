@@ -0,0 +1,105 @@
+use std::process::Command;
+
+use hello_rust::alloc_trace::{self, measure, Timeline, Tracer};
+use hello_rust::chapter::Chapter;
+use hello_rust::part04::Part04;
+
+// Installed here as well, so that these tests count allocations whether or
+// not the binary was built with the alloc-trace feature.
+#[global_allocator]
+static TRACER: Tracer = Tracer;
+
+#[test]
+fn allocations_and_frees_are_counted() {
+    assert!(alloc_trace::is_installed());
+    let (text, counts) = measure(|| String::with_capacity(16));
+    assert_eq!(counts.allocations, 1);
+    assert_eq!(counts.bytes_allocated, 16);
+    assert_eq!(counts.deallocations, 0);
+
+    let ((), counts) = measure(move || drop(text));
+    assert_eq!(counts.deallocations, 1);
+    assert_eq!(counts.bytes_freed, 16);
+}
+
+#[test]
+fn growing_a_vec_reallocates() {
+    let (numbers, counts) = measure(|| {
+        let mut numbers = Vec::with_capacity(1);
+        for number in 0..2u64 {
+            numbers.push(number);
+        }
+        numbers
+    });
+    assert_eq!(counts.allocations, 1);
+    assert_eq!(counts.reallocations, 1);
+    assert_eq!(numbers.len(), 2);
+    // Nothing at all for code that stays on the stack.
+    let (_, counts) = measure(|| [1u64; 32].iter().sum::<u64>());
+    assert!(counts.is_zero());
+}
+
+#[test]
+fn moving_a_string_allocates_nothing_and_cloning_it_once() {
+    let mut timeline = Timeline::new();
+    Part04.trace(&mut timeline).unwrap();
+
+    let moved = timeline.find("let s2 = s1").unwrap().counts;
+    assert!(moved.is_zero(), "{:?}", moved);
+    let cloned = timeline.find("let s3 = s2.clone()").unwrap().counts;
+    assert_eq!(cloned.allocations, 1);
+    assert_eq!(cloned.bytes_allocated, "Hello".len() as u64);
+    let dropped = timeline.find("drop(s2); drop(s3)").unwrap().counts;
+    assert_eq!(dropped.deallocations, 2);
+}
+
+#[test]
+fn any_chapter_is_one_step_by_default() {
+    let mut timeline = Timeline::new();
+    let registry = hello_rust::chapter::Registry::builtin();
+    registry.find("09").unwrap().trace(&mut timeline).unwrap();
+    assert_eq!(timeline.steps().len(), 1);
+    assert!(timeline.steps()[0].counts.allocations > 0);
+    let table = timeline.to_string();
+    assert!(table.starts_with("step"), "{}", table);
+    assert!(table.contains(&timeline.steps()[0].label));
+}
+
+#[test]
+fn the_cli_needs_the_feature() {
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["trace", "04"])
+        .output()
+        .unwrap();
+    if cfg!(feature = "alloc-trace") {
+        assert!(output.status.success());
+        let text = String::from_utf8(output.stdout).unwrap();
+        assert!(text.contains("let s2 = s1"), "{}", text);
+    } else {
+        assert!(!output.status.success());
+        let error = String::from_utf8(output.stderr).unwrap();
+        assert!(error.contains("--features alloc-trace"), "{}", error);
+    }
+}
+
+#[test]
+fn the_cli_skips_interactive_chapters_and_reports_missing_files() {
+    if !cfg!(feature = "alloc-trace") {
+        return;
+    }
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["trace", "02", "10"])
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap();
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        text.contains("not traced: the chapter waits for input"),
+        "{}",
+        text
+    );
+    // Chapter 10 reads hello.txt, which is only in the repository.
+    assert_eq!(output.status.code(), Some(1));
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(error.contains("hello.txt"), "{}", error);
+}
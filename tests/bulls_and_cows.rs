@@ -0,0 +1,94 @@
+use hello_rust::guessing::{play_bulls_and_cows, CodeGame, CodeRules, Game, Hint, MinimaxSolver};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn hint(bulls: u8, cows: u8) -> Hint {
+    Hint { bulls, cows }
+}
+
+#[test]
+fn repeated_digits_are_matched_once() {
+    let rules = CodeRules::new(4, 6, true).unwrap();
+    let code = |digits: &str| rules.parse(digits).unwrap();
+    assert_eq!(code("1122").hint(&code("2211")), hint(0, 4));
+    assert_eq!(code("1234").hint(&code("1111")), hint(1, 0));
+    assert_eq!(code("1123").hint(&code("3111")), hint(1, 2));
+    assert_eq!(code("0000").hint(&code("0000")), hint(4, 0));
+}
+
+#[test]
+fn guesses_are_checked_against_the_rules() {
+    let rules = CodeRules::default();
+    assert!(rules.parse(" 0427\n").is_ok());
+    assert!(rules.parse("042").is_err());
+    assert!(rules.parse("04a7").is_err());
+    assert!(rules.parse("0447").is_err());
+    assert!(CodeRules::new(4, 6, true).unwrap().parse("0445").is_ok());
+    assert!(CodeRules::new(4, 6, true).unwrap().parse("0467").is_err());
+    assert!(CodeRules::new(11, 10, false).is_err());
+    assert!(CodeRules::new(5, 4, false).is_err());
+    assert_eq!(CodeRules::default().code_count(), 5040);
+    assert_eq!(CodeRules::default().all_codes().len(), 5040);
+}
+
+#[test]
+fn invalid_guesses_do_not_count() {
+    let rules = CodeRules::default();
+    let mut game =
+        CodeGame::with_secret(rules.parse("1234").unwrap(), rules).with_max_attempts(Some(2));
+    assert!(game.play("12").is_err());
+    assert_eq!(game.play("4321"), Ok(hint(0, 4)));
+    assert_eq!(game.attempts_left(), Some(1));
+    assert_eq!(game.play("1243"), Ok(hint(2, 2)));
+    assert!(game.is_lost());
+    assert_eq!(game.solution(), "The code was 1234");
+}
+
+// Knuth showed that minimax needs at most five guesses for classic
+// Mastermind, whatever the code. Trying all 1296 codes is slow without
+// optimisations, so every 25th one is tried.
+#[test]
+fn solver_finds_mastermind_codes_in_five_guesses() {
+    let rules = CodeRules::new(4, 6, true).unwrap();
+    for secret in rules.all_codes().into_iter().step_by(25) {
+        let mut solver = MinimaxSolver::new(rules).unwrap();
+        let mut game = CodeGame::with_secret(secret.clone(), rules);
+        while !game.is_won() {
+            let guess = solver.next_guess().unwrap();
+            let hint = game.guess_code(&guess);
+            solver.feedback(&guess, hint);
+        }
+        assert!(game.attempts() <= 5, "{} took {}", secret, game.attempts());
+    }
+}
+
+#[test]
+fn solver_notices_impossible_hints() {
+    let rules = CodeRules::new(3, 4, false).unwrap();
+    let mut solver = MinimaxSolver::new(rules).unwrap();
+    let guess = solver.next_guess().unwrap();
+    solver.feedback(&guess, hint(2, 1));
+    solver.feedback(&guess, hint(0, 0));
+    assert_eq!(solver.next_guess(), None);
+}
+
+#[test]
+fn the_front_end_shares_the_guessing_loop() {
+    let rules = CodeRules::new(2, 4, false).unwrap();
+    let secret = CodeGame::new(&mut StdRng::seed_from_u64(3), rules)
+        .secret()
+        .to_string();
+    let input = format!("7\n{}\n", secret);
+    let mut out = Vec::new();
+    play_bulls_and_cows(
+        &mut input.as_bytes(),
+        &mut out,
+        &mut StdRng::seed_from_u64(3),
+        rules,
+        None,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Please type 2 digits from 0 to 3!"), "{}", out);
+    assert!(out.ends_with("You win!\n"), "{}", out);
+}
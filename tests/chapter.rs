@@ -0,0 +1,21 @@
+use std::fs;
+use std::path::PathBuf;
+
+use hello_rust::chapter::Registry;
+use hello_rust::i18n::{Catalog, ENGLISH};
+
+// Every partNN.rs file starts with its title between two rows of slashes.
+#[test]
+fn every_title_matches_its_banner_and_catalog() {
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+    let english = Catalog::builtin(ENGLISH).unwrap();
+    for chapter in Registry::builtin().iter() {
+        let file = src.join(format!("part{}.rs", chapter.id()));
+        let text = fs::read_to_string(&file).unwrap();
+        let banner = text.lines().nth(1).and_then(|line| line.strip_prefix("// "));
+        assert_eq!(banner, Some(chapter.title()), "{}", file.display());
+
+        let id = format!("chapter.{}.title", chapter.id());
+        assert_eq!(english.get(&id), Some(chapter.title()), "{}", id);
+    }
+}
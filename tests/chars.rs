@@ -0,0 +1,82 @@
+use hello_rust::chars::{
+    inspect, parse_code_point, to_char, Category, CharError, CharInfo, HIGH_SCALARS, LOW_SCALARS,
+};
+
+#[test]
+fn chapter_three_chars_are_taken_apart() {
+    let chars = inspect("zℤ").unwrap();
+    assert_eq!(chars.len(), 2);
+    assert_eq!(chars[0].code_point(), 0x7A);
+    assert_eq!(chars[0].utf8(), [0x7A]);
+    assert_eq!(chars[0].category(), Some(Category::LowercaseLetter));
+    assert_eq!(chars[1].code_point(), 0x2124);
+    assert_eq!(chars[1].utf8(), [0xE2, 0x84, 0xA4]);
+    assert_eq!(chars[1].utf16(), [0x2124]);
+    assert_eq!(chars[1].range(), LOW_SCALARS);
+    assert_eq!(
+        chars[1].to_string(),
+        "'ℤ' U+2124\n  UTF-8     e2 84 a4 (3 bytes)\n  UTF-16    2124 (1 unit)\n  \
+         category  Lu (uppercase letter)\n  range     U+0000..=U+D7FF"
+    );
+}
+
+#[test]
+fn scalars_above_u_ffff_take_a_surrogate_pair() {
+    let crab = CharInfo::new('🦀');
+    assert_eq!(crab.utf8(), [0xF0, 0x9F, 0xA6, 0x80]);
+    assert_eq!(crab.utf16(), [0xD83E, 0xDD80]);
+    assert_eq!(crab.range(), HIGH_SCALARS);
+    assert_eq!(crab.category(), Some(Category::OtherSymbol));
+    // Text is taken apart by scalar, so a letter with a combining accent is
+    // two chars.
+    let chars = inspect("e\u{301}").unwrap();
+    assert_eq!(chars[1].category(), Some(Category::NonspacingMark));
+}
+
+#[test]
+fn code_points_are_checked() {
+    assert_eq!(parse_code_point("U+2124"), Ok('ℤ'));
+    assert_eq!(parse_code_point("\\u{1F980}"), Ok('🦀'));
+    assert_eq!(parse_code_point("0x41"), Ok('A'));
+    assert_eq!(parse_code_point("U+D7FF"), Ok('\u{D7FF}'));
+    assert_eq!(parse_code_point("U+E000"), Ok('\u{E000}'));
+    assert_eq!(
+        parse_code_point("U+D800"),
+        Err(CharError::Surrogate(0xD800))
+    );
+    assert_eq!(to_char(0xDFFF), Err(CharError::Surrogate(0xDFFF)));
+    assert_eq!(to_char(0x110000), Err(CharError::TooLarge(0x110000)));
+    assert!(matches!(
+        parse_code_point("U+XYZ"),
+        Err(CharError::NotACodePoint(_))
+    ));
+    assert!(CharError::Surrogate(0xD800)
+        .to_string()
+        .starts_with("U+D800 is a surrogate"));
+
+    // Code points and text are told apart.
+    let chars: Vec<char> = inspect("U+48 0x49")
+        .unwrap()
+        .iter()
+        .map(|info| info.c)
+        .collect();
+    assert_eq!(chars, ['H', 'I']);
+    assert_eq!(inspect("U+").unwrap().len(), 2);
+    assert_eq!(inspect("U+48 U+DC00"), Err(CharError::Surrogate(0xDC00)));
+}
+
+#[test]
+fn categories_outside_the_table_are_unknown() {
+    assert_eq!(Category::of('\n'), Some(Category::Control));
+    assert_eq!(Category::of('_'), Some(Category::ConnectorPunctuation));
+    assert_eq!(Category::of('€'), Some(Category::CurrencySymbol));
+    assert_eq!(Category::of('ß'), Some(Category::LowercaseLetter));
+    assert_eq!(Category::of('\u{E000}'), Some(Category::PrivateUse));
+    // Latin Extended-A and -B are not in the table, but their letters are
+    // told apart all the same.
+    assert_eq!(Category::of('Ā'), Some(Category::UppercaseLetter));
+    assert_eq!(Category::of('ł'), Some(Category::LowercaseLetter));
+    assert_eq!(Category::of('ǅ'), Some(Category::TitlecaseLetter));
+    assert_eq!(Category::of('ǃ'), Some(Category::OtherLetter));
+    assert_eq!(Category::of('\u{10FFFF}'), None);
+}
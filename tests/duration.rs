@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use hello_rust::duration::{ParseSpanError, Span};
+
+fn span(text: &str) -> Result<Span, ParseSpanError> {
+    text.parse()
+}
+
+#[test]
+fn spans_are_read_in_any_mix_of_units() {
+    assert_eq!(span("3h"), Ok(Span::from_hours(3)));
+    assert_eq!(span("1h30m"), Ok(Span::from_mins(90)));
+    assert_eq!(span("90s"), Ok(Span::from_secs(90)));
+    assert_eq!(span("2d4h"), Ok(Span::from_hours(52)));
+    assert_eq!(span(" 1h 30m "), Ok(Span::from_mins(90)));
+    assert_eq!(span("0s"), Ok(Span::ZERO));
+}
+
+#[test]
+fn malformed_spans_are_rejected() {
+    assert_eq!(span(""), Err(ParseSpanError::Empty));
+    assert_eq!(span("90"), Err(ParseSpanError::MissingUnit));
+    assert_eq!(span("1h30"), Err(ParseSpanError::MissingUnit));
+    assert_eq!(span("1 h"), Err(ParseSpanError::MissingUnit));
+    assert_eq!(span("h"), Err(ParseSpanError::MissingNumber('h')));
+    assert_eq!(span("3w"), Err(ParseSpanError::UnknownUnit('w')));
+    assert_eq!(span("30m1h"), Err(ParseSpanError::OutOfOrder('h')));
+    assert_eq!(span("1h1h"), Err(ParseSpanError::OutOfOrder('h')));
+    assert_eq!(span("213503982334602d"), Err(ParseSpanError::TooLong));
+    assert_eq!(span("99999999999999999999s"), Err(ParseSpanError::TooLong));
+}
+
+#[test]
+fn spans_are_shown_in_the_largest_units() {
+    assert_eq!(Span::from_secs(90).to_string(), "1m30s");
+    assert_eq!(Span::from_hours(52).to_string(), "2d4h");
+    assert_eq!(Span::from_secs(93_601).to_string(), "1d2h1s");
+    assert_eq!(Span::ZERO.to_string(), "0s");
+    // What is shown reads back as the same span.
+    for secs in [1, 59, 60, 3_599, 86_400, 1_000_000] {
+        let shown = Span::from_secs(secs).to_string();
+        assert_eq!(span(&shown), Ok(Span::from_secs(secs)));
+    }
+}
+
+#[test]
+fn arithmetic_is_checked() {
+    const THREE_HOURS: Span = Span::from_hours(3);
+    assert_eq!(Span::HOUR.checked_mul(3), Some(THREE_HOURS));
+    assert_eq!(THREE_HOURS.checked_add(Span::MINUTE), span("3h1m").ok());
+    assert_eq!(Span::WEEK.checked_sub(Span::DAY), Some(Span::from_days(6)));
+    assert_eq!(Span::MINUTE.checked_sub(Span::HOUR), None);
+    assert_eq!(Span::DAY.checked_mul(u64::MAX), None);
+    assert_eq!(Span::from_secs(u64::MAX).checked_add(Span::SECOND), None);
+
+    assert_eq!(Duration::from(THREE_HOURS), Duration::from_secs(10_800));
+    assert_eq!(Span::from(Duration::from_millis(1_999)), Span::SECOND);
+}
+
+// Checked in release builds too, where `u64::MAX * 60` would wrap around.
+#[test]
+#[should_panic(expected = "does not fit")]
+fn spans_too_long_panic() {
+    let mins = std::hint::black_box(u64::MAX);
+    Span::from_mins(mins);
+}
@@ -0,0 +1,91 @@
+// Golden-output tests: every non-interactive chapter is run into a buffer and
+// compared with the checked-in tests/golden/partNN.txt file.
+//
+// After an intentional change to a chapter's output, regenerate the files with
+//
+//     UPDATE_GOLDEN=1 cargo test --test golden
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use hello_rust::chapter::Registry;
+
+#[test]
+fn chapter_output_matches_golden_files() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let golden_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut failures = Vec::new();
+
+    for chapter in Registry::builtin().iter() {
+        if chapter.interactive() {
+            continue;
+        }
+
+        let mut output = Vec::new();
+        chapter
+            .run(&mut output)
+            .unwrap_or_else(|error| panic!("chapter {} failed: {}", chapter.id(), error));
+        let actual = String::from_utf8(output).expect("chapter output should be UTF-8");
+
+        let path = golden_dir.join(format!("part{}.txt", chapter.id()));
+        if update {
+            fs::create_dir_all(&golden_dir).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "chapter {} output differs from {} (- expected, + actual):\n{}",
+                chapter.id(),
+                path.display(),
+                diff(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!(
+                "chapter {} has no golden file at {}; run with UPDATE_GOLDEN=1 to create it",
+                chapter.id(),
+                path.display()
+            )),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// Line diff based on the longest common subsequence. Unchanged lines are
+// printed with two spaces so the changed ones keep their context.
+fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
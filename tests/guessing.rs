@@ -0,0 +1,83 @@
+use hello_rust::cli::CliError;
+use hello_rust::guessing::{GuessingGame, Outcome, DEFAULT_RANGE};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn same_seed_picks_same_secret() {
+    let first = GuessingGame::new(&mut StdRng::seed_from_u64(42));
+    let second = GuessingGame::new(&mut StdRng::seed_from_u64(42));
+    assert_eq!(first.secret(), second.secret());
+    assert!(DEFAULT_RANGE.contains(&first.secret()));
+}
+
+#[test]
+fn guesses_are_compared_with_the_secret() {
+    let mut game = GuessingGame::with_secret(37, DEFAULT_RANGE);
+    assert_eq!(game.guess("50\n"), Outcome::TooBig);
+    assert_eq!(game.guess("  20 "), Outcome::TooSmall);
+    assert_eq!(game.guess("37"), Outcome::Win);
+    assert!(game.is_won());
+    assert_eq!(game.attempts(), 3);
+}
+
+#[test]
+fn invalid_input_is_not_an_attempt() {
+    let mut game = GuessingGame::with_secret(5, DEFAULT_RANGE);
+    assert_eq!(game.guess("five"), Outcome::Invalid);
+    assert_eq!(game.guess("-5"), Outcome::Invalid);
+    assert_eq!(game.attempts(), 0);
+}
+
+#[test]
+fn guesses_after_a_win_are_ignored() {
+    let mut game = GuessingGame::with_secret(5, DEFAULT_RANGE);
+    assert_eq!(game.guess("5"), Outcome::Win);
+    assert_eq!(game.guess("9"), Outcome::Win);
+    assert_eq!(game.attempts(), 1);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let mut game = GuessingGame::with_secret(5, DEFAULT_RANGE).with_max_attempts(Some(2));
+    assert_eq!(game.guess("1"), Outcome::TooSmall);
+    assert_eq!(game.attempts_left(), Some(1));
+    assert_eq!(game.guess("9"), Outcome::Lost);
+    assert!(game.is_lost());
+    assert_eq!(game.guess("5"), Outcome::Lost);
+    assert_eq!(game.attempts(), 2);
+}
+
+#[test]
+fn ranges_parse_inclusive_and_exclusive() {
+    use hello_rust::numbers::parse_range;
+    assert_eq!(parse_range("1..=500"), Ok(1..=500));
+    assert_eq!(parse_range("1..501"), Ok(1..=500));
+    assert_eq!(parse_range("1-500"), Ok(1..=500));
+    assert!(parse_range("500..=1").is_err());
+    assert!(parse_range("0..0").is_err());
+}
+
+#[test]
+fn every_limit_flag_refuses_zero() {
+    let commands: [&[&str]; 4] = [
+        &["play", "--max-attempts"],
+        &["bulls-and-cows", "--max-attempts"],
+        &["hangman", "--word", "hello", "--max-misses"],
+        &["simulate", "--max-attempts"],
+    ];
+    for command in commands {
+        let limit = |value: &str| {
+            let mut args: Vec<String> = command.iter().map(|arg| arg.to_string()).collect();
+            args.push(value.to_string());
+            hello_rust::cli::parse(&args)
+        };
+        let flag = command[command.len() - 1];
+        assert!(
+            matches!(limit("0"), Err(CliError::InvalidValue { flag: refused, .. }) if refused == flag),
+            "{:?}",
+            command
+        );
+        assert!(limit("1").is_ok(), "{:?}", command);
+    }
+}
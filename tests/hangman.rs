@@ -0,0 +1,112 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use hello_rust::cli::CliError;
+use hello_rust::guessing::{play_hangman, Game, HangmanGame, Letters, NoLetters, WordList};
+
+#[test]
+fn cyrillic_words_are_playable() {
+    let mut game = HangmanGame::new("Здравствуйте", Letters::Scalars)
+        .unwrap()
+        .with_max_misses(Some(3));
+    assert_eq!(game.len(), 12);
+    assert_eq!(game.play("в").unwrap().found, 2);
+    // Upper and lower case are the same letter.
+    assert_eq!(game.play("з").unwrap().mask, "З _ _ _ в _ _ в _ _ _ _");
+    assert!(game.play("В").is_err());
+    assert_eq!(game.play("ж").unwrap().found, 0);
+    assert_eq!(game.attempts_left(), Some(2));
+    for letter in ["д", "р", "а", "с", "т", "у", "й", "е"] {
+        game.play(letter).unwrap();
+    }
+    assert!(game.is_won());
+    assert_eq!(game.mask(), "З д р а в с т в у й т е");
+}
+
+#[test]
+fn combining_accents_belong_to_their_letter() {
+    // "x́" has no precomposed form, so it stays an "x" and a combining acute
+    // accent.
+    let word = "tax\u{301}";
+    assert_eq!(HangmanGame::new(word, Letters::Scalars).unwrap().len(), 4);
+
+    let mut game = HangmanGame::new(word, Letters::Graphemes).unwrap();
+    assert_eq!(game.len(), 3);
+    assert_eq!(game.play("x").unwrap().found, 0);
+    assert_eq!(game.play("x\u{301}").unwrap().mask, "_ _ x\u{301}");
+}
+
+#[test]
+fn precomposed_and_decomposed_accents_are_the_same_letter() {
+    // The word has "e" and a combining accent, the guess a precomposed "é".
+    let mut game = HangmanGame::new("cafe\u{301}", Letters::Scalars).unwrap();
+    assert_eq!(game.len(), 4);
+    assert_eq!(game.play("\u{e9}").unwrap().found, 1);
+
+    let mut game = HangmanGame::new("caf\u{e9}", Letters::Graphemes).unwrap();
+    assert_eq!(game.play("CAFE\u{301}").unwrap().found, 4);
+    assert!(game.is_won());
+}
+
+#[test]
+fn words_without_letters_are_refused() {
+    assert_eq!(
+        HangmanGame::new("", Letters::Graphemes).err(),
+        Some(NoLetters)
+    );
+    assert_eq!(
+        HangmanGame::new("42 - 7", Letters::Scalars).err(),
+        Some(NoLetters)
+    );
+    assert_eq!(
+        WordList::from_str("2024\nhello\n").unwrap().words(),
+        ["hello"]
+    );
+
+    let args = ["hangman", "--word", "1234"].map(String::from);
+    assert!(matches!(
+        hello_rust::cli::parse(&args),
+        Err(CliError::InvalidValue { flag: "--word", .. })
+    ));
+}
+
+#[test]
+fn misses_run_out() {
+    let mut game = HangmanGame::new("crème brûlée", Letters::Graphemes)
+        .unwrap()
+        .with_max_misses(Some(2));
+    assert_eq!(game.mask(), "_ _ _ _ _   _ _ _ _ _ _");
+    assert_eq!(game.play("crème").unwrap().found, 0);
+    assert!(game.play("?").is_err());
+    assert_eq!(game.play("x").unwrap().found, 0);
+    assert!(game.is_lost());
+    assert_eq!(game.solution(), "The word was crème brûlée");
+}
+
+#[test]
+fn word_lists_skip_comments_and_blank_lines() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/words/greetings.txt");
+    let list = WordList::load(path).unwrap();
+    assert_eq!(list.words(), ["Здравствуйте", "hello"]);
+    assert!(WordList::load("tests/words/missing.txt").is_err());
+}
+
+#[test]
+fn the_front_end_shares_the_guessing_loop() {
+    let game = HangmanGame::new("hello", Letters::Graphemes).unwrap();
+    let mut out = Vec::new();
+    play_hangman(&mut &b"l\nq\nHELLO\n"[..], &mut out, game).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.starts_with("Guess the word: _ _ _ _ _ (5 letters).\n"),
+        "{}",
+        out
+    );
+    assert!(out.contains("Found 2 letters. _ _ l l _\n"), "{}", out);
+    assert!(
+        out.contains("Not in the word. _ _ l l _\nAttempts left: 5\n"),
+        "{}",
+        out
+    );
+    assert!(out.ends_with("You win!\n"), "{}", out);
+}
@@ -0,0 +1,73 @@
+use hello_rust::guessing::{
+    Clue, Game, GuessingGame, HintSettings, HintedGame, Outcome, Proximity, Question,
+};
+
+fn hinted(secret: u32, budget: u32) -> HintedGame {
+    let settings = HintSettings {
+        budget,
+        hot_cold: true,
+    };
+    HintedGame::new(GuessingGame::with_secret(secret, 0..=100), settings)
+}
+
+#[test]
+fn proximity_is_relative_to_the_range() {
+    assert_eq!(Proximity::of(48, 50, &(0..=100)), Proximity::Hot);
+    assert_eq!(Proximity::of(40, 50, &(0..=100)), Proximity::Warm);
+    assert_eq!(Proximity::of(20, 50, &(0..=100)), Proximity::Cold);
+    assert_eq!(Proximity::of(0, 50, &(0..=100)), Proximity::Freezing);
+    // The same distance is hot on a bigger range.
+    assert_eq!(Proximity::of(20, 50, &(0..=1000)), Proximity::Hot);
+    assert_eq!(Proximity::of(9, 5, &(5..=5)), Proximity::Freezing);
+}
+
+#[test]
+fn questions_are_parsed_and_answered() {
+    assert_eq!("even".parse(), Ok(Question::Even));
+    assert_eq!(" div 3".parse(), Ok(Question::DivisibleBy(3)));
+    assert_eq!("divisible by 7".parse(), Ok(Question::DivisibleBy(7)));
+    assert!("div 0".parse::<Question>().is_err());
+    assert!("div 1".parse::<Question>().is_err());
+    assert!("prime".parse::<Question>().is_err());
+    assert!(Question::Even.answer(42));
+    assert!(Question::DivisibleBy(3).answer(42));
+    assert!(!Question::DivisibleBy(5).answer(42));
+}
+
+#[test]
+fn hints_do_not_count_as_attempts() {
+    let mut game = hinted(42, 1);
+    assert_eq!(
+        game.play("hint div 3\n"),
+        Ok(Clue::Answer {
+            question: Question::DivisibleBy(3),
+            yes: true,
+            hints_left: 0,
+        })
+    );
+    assert_eq!(game.attempts(), 0);
+    assert_eq!(game.hints_used(), 1);
+    assert!(game.play("hint even").is_err());
+    assert!(hinted(42, 0).play("hint even").is_err());
+
+    assert_eq!(
+        game.play("40"),
+        Ok(Clue::Guess(Outcome::TooSmall, Some(Proximity::Hot)))
+    );
+    assert_eq!(game.play("42"), Ok(Clue::Guess(Outcome::Win, None)));
+    assert_eq!(game.attempts(), 2);
+}
+
+#[test]
+fn clues_read_like_sentences() {
+    let mut game = hinted(42, 2);
+    assert_eq!(
+        game.play("hint even").unwrap().to_string(),
+        "Yes, it is even. 1 hint left."
+    );
+    assert_eq!(
+        game.play("hint div 5").unwrap().to_string(),
+        "No, it is not divisible by 5. 0 hints left."
+    );
+    assert_eq!(game.play("90").unwrap().to_string(), "Too big! (freezing)");
+}
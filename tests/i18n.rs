@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use hello_rust::guessing::{play_hangman, play_turns, GuessingGame, HangmanGame, Letters};
+use hello_rust::i18n::{self, Catalog, CatalogError, Messages, ENGLISH};
+
+fn locale_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/locales"))
+}
+
+#[test]
+fn every_catalog_translates_every_message() {
+    let english = Catalog::english();
+    for entry in fs::read_dir(locale_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let language = path.file_stem().unwrap().to_str().unwrap();
+        let messages = Messages::load(locale_dir(), language)
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+        assert!(
+            messages.untranslated().is_empty(),
+            "{} does not translate {:?}",
+            language,
+            messages.untranslated()
+        );
+        // Chapter titles are the only extra messages a language may have.
+        let file = Catalog::load(locale_dir(), language).unwrap();
+        for id in file.ids() {
+            assert!(
+                english.get(id).is_some(),
+                "{} has an unknown message '{}'",
+                language,
+                id
+            );
+        }
+    }
+    assert_eq!(Catalog::load(locale_dir(), ENGLISH).unwrap(), english);
+    assert_eq!(
+        Catalog::builtin("es"),
+        Some(Catalog::load(locale_dir(), "es").unwrap())
+    );
+}
+
+#[test]
+fn missing_messages_fall_back_to_english() {
+    let partial = Catalog::parse("xx", "# only one\nguess.win = Won!\n").unwrap();
+    let messages = Messages::new(partial);
+    assert_eq!(messages.get("guess.win"), Some("Won!"));
+    assert_eq!(messages.get("outcome.too-small"), Some("Too small"));
+    assert_eq!(messages.get("no.such.message"), None);
+    assert!(messages.untranslated().contains(&"guess.prompt"));
+    assert!(matches!(
+        Catalog::load(locale_dir(), "xx"),
+        Err(CatalogError::UnknownLanguage(_))
+    ));
+    assert!(matches!(
+        Catalog::load(locale_dir(), "../Cargo"),
+        Err(CatalogError::UnknownLanguage(_))
+    ));
+}
+
+#[test]
+fn broken_catalogs_name_the_line() {
+    match Catalog::parse("xx", "a = 1\n\nno equals sign\n") {
+        Err(CatalogError::Parse { line, .. }) => assert_eq!(line, 3),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+    assert!(Catalog::parse("xx", "a = 1\na = 2\n").is_err());
+    assert!(Catalog::parse("xx", "two words = 1\n").is_err());
+}
+
+// Tests run on threads of their own, so changing the language here leaves
+// the other tests in English.
+#[test]
+fn the_guessing_loop_speaks_the_chosen_language() {
+    i18n::set_messages(Messages::load(locale_dir(), "es").unwrap());
+    let mut game = GuessingGame::with_secret(7, 0..=10).with_max_attempts(Some(3));
+    let mut out = Vec::new();
+    play_turns(
+        &mut game,
+        &mut &b"x\n9\n7\n"[..],
+        &mut out,
+        &mut |_, _, _| Ok(()),
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.starts_with("Escribe tu número\nHas dicho: x\n"),
+        "{}",
+        out
+    );
+    assert!(out.contains("¡Escribe un número!\n"), "{}", out);
+    assert!(
+        out.contains("¡Demasiado grande!\nIntentos restantes: 2\n"),
+        "{}",
+        out
+    );
+    assert!(out.ends_with("¡Has ganado!\n"), "{}", out);
+    assert_eq!(
+        i18n::format("game.solution", &[("secret", &7)]),
+        "El número secreto era 7"
+    );
+}
+
+#[test]
+fn every_game_has_its_messages_in_the_catalog() {
+    i18n::set_messages(Messages::load(locale_dir(), "es").unwrap());
+    let game = HangmanGame::new("hola", Letters::Graphemes).unwrap();
+    let mut out = Vec::new();
+    play_hangman(&mut &b"o\nz\n"[..], &mut out, game).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.contains("Escribe una letra o la palabra entera\n"),
+        "{}",
+        out
+    );
+    assert!(out.contains("No está en la palabra. _ o _ _\n"), "{}", out);
+}
+
+#[test]
+fn lang_flag_translates_chapter_banners() {
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["--lang", "es", "run", "01"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("===== 01 Primeros pasos =====\n"),
+        "{}",
+        stdout
+    );
+
+    // The shipped catalogs are built in, so any directory will do.
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["--lang", "es", "list"])
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["--lang", "xx", "list"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,72 @@
+use std::process::Command;
+
+use hello_rust::layout::{report, Row};
+
+fn row(name: &str) -> Row {
+    report()
+        .into_iter()
+        .flat_map(|section| section.rows)
+        .find(|row| row.name == name)
+        .unwrap_or_else(|| panic!("no row for {}", name))
+}
+
+#[test]
+fn primitives_have_their_documented_sizes() {
+    for (name, size) in [
+        ("i8", 1),
+        ("u16", 2),
+        ("i32", 4),
+        ("f64", 8),
+        ("u128", 16),
+        ("bool", 1),
+        ("char", 4),
+        ("()", 0),
+    ] {
+        assert_eq!(row(name).size, size, "{}", name);
+    }
+    assert_eq!(row("usize").size, std::mem::size_of::<*const u8>());
+    assert_eq!(row("&str").size, 2 * row("usize").size);
+}
+
+#[test]
+fn padding_is_accounted_for() {
+    let tuple = row("(i32, f64, u8)");
+    assert_eq!(tuple.size % tuple.align, 0);
+    assert!(tuple.note.unwrap().starts_with("fields take 13 bytes"));
+    assert_eq!(row("[i32; 5]").size, 20);
+    // Every struct is at least as big as its fields.
+    assert!(row("SeaCreature").size >= 1 + 2 * row("String").size + 2 * 4);
+}
+
+#[test]
+fn niches_are_flagged() {
+    // The null pointer optimisation is guaranteed for references and boxes.
+    assert_eq!(row("Option<&i32>").niche, Some(true));
+    assert_eq!(row("Option<Box<i32>>").niche, Some(true));
+    assert_eq!(row("Option<NonZeroU32>").niche, Some(true));
+    // Every bit pattern of an i32 is a valid i32, so None needs a tag.
+    assert_eq!(row("Option<i32>").niche, Some(false));
+    assert_eq!(row("Option<i32>").size, 8);
+    // Not an enum, nothing to flag.
+    assert_eq!(row("i32").niche, None);
+    // Claw takes 5 bytes, an i32 and a Size, which leaves room for a tag
+    // byte before the padding.
+    assert_eq!(row("Weapon").niche, Some(false));
+    assert!(row("Weapon").note.as_deref().unwrap().contains("5 bytes"));
+    // Paste is as big as the enum, so its String has to hold the tag.
+    assert_eq!(row("WebEvent").niche, Some(true));
+}
+
+#[test]
+fn the_cli_prints_every_section() {
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .arg("layout")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    for title in ["Primitives", "Compound types (part03)", "Options"] {
+        assert!(text.contains(title), "{}", title);
+    }
+    assert!(text.contains("Option<&i32>"));
+}
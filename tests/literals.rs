@@ -0,0 +1,84 @@
+use hello_rust::literals::{convert, parse_literal, Base, IntType, Literal, LiteralError};
+
+#[test]
+fn chapter_three_literals_parse() {
+    let value = |input: &str| parse_literal(input).unwrap().magnitude;
+    assert_eq!(value("0xff"), 255);
+    assert_eq!(value("0o77"), 63);
+    assert_eq!(value("0b10111_011"), 187);
+    assert_eq!(value("1_000_000"), 1_000_000);
+    assert_eq!(value("0x_ff"), 255);
+    assert_eq!(
+        parse_literal("-0b1000_0000_i8"),
+        Ok(Literal {
+            negative: true,
+            magnitude: 128,
+            base: Base::Binary,
+            suffix: Some(IntType::I8),
+        })
+    );
+}
+
+#[test]
+fn malformed_literals_are_rejected() {
+    assert_eq!(parse_literal(""), Err(LiteralError::Empty));
+    assert_eq!(parse_literal("0x"), Err(LiteralError::NoDigits));
+    assert_eq!(parse_literal("_1"), Err(LiteralError::NoDigits));
+    assert_eq!(
+        parse_literal("0b102"),
+        Err(LiteralError::InvalidDigit {
+            digit: '2',
+            base: Base::Binary
+        })
+    );
+    assert_eq!(
+        parse_literal("1e3").unwrap_err().to_string(),
+        "'e' is not a decimal digit"
+    );
+    assert!(matches!(
+        parse_literal("7u7"),
+        Err(LiteralError::UnknownType(_))
+    ));
+    assert_eq!(
+        parse_literal("340282366920938463463374607431768211456"),
+        Err(LiteralError::TooLarge)
+    );
+}
+
+#[test]
+fn values_must_fit_their_type() {
+    assert!(convert("255u8", IntType::I32).is_ok());
+    assert_eq!(
+        convert("256u8", IntType::I32),
+        Err(LiteralError::DoesNotFit(IntType::U8))
+    );
+    assert!(convert("-128i8", IntType::I32).is_ok());
+    assert!(convert("-129i8", IntType::I32).is_err());
+    assert!(convert("-1u32", IntType::I32).is_err());
+    assert!(convert("-0u32", IntType::I32).is_ok());
+    // Without a suffix, the type given is used.
+    assert!(convert("3000000000", IntType::I32).is_err());
+    assert!(convert("3000000000", IntType::U32).is_ok());
+    assert!(convert("340282366920938463463374607431768211455", IntType::U128).is_ok());
+}
+
+#[test]
+fn values_are_shown_in_every_base_and_as_bits() {
+    let value = convert("-1i16", IntType::I32).unwrap();
+    assert_eq!(value.to_string(), "-1i16");
+    assert_eq!(value.bits(), 0xffff);
+    assert_eq!(value.bit_pattern(), "1111_1111_1111_1111");
+    assert_eq!(value.in_base(Base::Octal), "-0o1");
+
+    let value = convert("-128i8", IntType::I32).unwrap();
+    assert_eq!(value.bit_pattern(), "1000_0000");
+    let value = convert("0xff", IntType::U8).unwrap();
+    assert_eq!(value.in_base(Base::Binary), "0b11111111");
+    assert_eq!(value.in_base(Base::Decimal), "255");
+    assert_eq!(
+        convert("-170141183460469231731687303715884105728i128", IntType::I32)
+            .unwrap()
+            .bits(),
+        1 << 127
+    );
+}
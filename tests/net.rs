@@ -0,0 +1,238 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::SocketAddr;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use hello_rust::guessing::net::{Client, Reply, Request, Server, ServerConfig};
+
+// A server on a free localhost port, running on its own thread.
+fn start_server(config: ServerConfig) -> (SocketAddr, thread::JoinHandle<()>) {
+    let server = Server::bind("127.0.0.1:0", config).unwrap();
+    let addr = server.local_addr().unwrap();
+    let handle = thread::spawn(move || server.run().unwrap());
+    (addr, handle)
+}
+
+fn joined(addr: SocketAddr, name: &str) -> Client {
+    let mut client = Client::connect(addr).unwrap();
+    match client.join(name).unwrap() {
+        Reply::Welcome { name: joined, .. } => assert_eq!(joined, name),
+        other => panic!("expected WELCOME, got {}", other),
+    }
+    client
+}
+
+#[test]
+fn messages_round_trip_through_text() {
+    let replies = [
+        Reply::Welcome {
+            round: 3,
+            range: 1..=100,
+            name: "Ferris the crab".to_string(),
+        },
+        Reply::TooSmall(4),
+        Reply::TooBig(90),
+        Reply::Correct(37),
+        Reply::Winner {
+            secret: 37,
+            attempts: 6,
+            name: "Ferris the crab".to_string(),
+        },
+        Reply::Round {
+            round: 4,
+            range: 1..=100,
+        },
+        Reply::Error("the name 'x' is taken".to_string()),
+        Reply::Bye,
+    ];
+    for reply in replies {
+        assert_eq!(reply.to_string().parse::<Reply>(), Ok(reply));
+    }
+    assert_eq!("GUESS 42\r\n".parse(), Ok(Request::Guess(42)));
+    assert!("GUESS forty".parse::<Request>().is_err());
+    assert!("ROUND 1 9 2".parse::<Reply>().is_err());
+}
+
+#[test]
+fn first_correct_guess_wins_the_round_for_everyone() {
+    // 1..=2 has two secrets, so both outcomes of a wrong guess show up.
+    let (addr, server) = start_server(ServerConfig {
+        range: 1..=2,
+        rounds: Some(2),
+        seed: Some(5),
+    });
+    let mut alice = joined(addr, "alice");
+    let mut bob = joined(addr, "bob");
+
+    for round in 1..=2 {
+        // Alice tries 1 first; if that was wrong, the secret is 2.
+        alice.send(&Request::Guess(1)).unwrap();
+        let secret = match alice.receive().unwrap() {
+            Reply::Correct(1) => 1,
+            Reply::TooSmall(1) => {
+                alice.send(&Request::Guess(2)).unwrap();
+                assert_eq!(alice.receive(), Some(Reply::Correct(2)));
+                2
+            }
+            other => panic!("unexpected reply {}", other),
+        };
+
+        let winner = Reply::Winner {
+            secret,
+            attempts: secret,
+            name: "alice".to_string(),
+        };
+        assert_eq!(alice.receive(), Some(winner.clone()));
+        assert_eq!(bob.receive(), Some(winner));
+        if round == 1 {
+            let next = Reply::Round {
+                round: 2,
+                range: 1..=2,
+            };
+            assert_eq!(alice.receive(), Some(next.clone()));
+            assert_eq!(bob.receive(), Some(next));
+        }
+    }
+
+    // The second round was the last one.
+    assert_eq!(bob.receive(), Some(Reply::Bye));
+    assert_eq!(bob.receive(), None);
+    assert_eq!(alice.receive(), Some(Reply::Bye));
+    server.join().unwrap();
+}
+
+#[test]
+fn a_new_round_resets_the_attempts() {
+    let (addr, _server) = start_server(ServerConfig {
+        range: 7..=7,
+        rounds: None,
+        seed: None,
+    });
+    let mut alice = joined(addr, "alice");
+    let mut bob = joined(addr, "bob");
+
+    bob.send(&Request::Guess(3)).unwrap();
+    assert_eq!(bob.receive(), Some(Reply::TooSmall(3)));
+    alice.send(&Request::Guess(7)).unwrap();
+    assert_eq!(alice.receive(), Some(Reply::Correct(7)));
+
+    let round_two = Reply::Round {
+        round: 2,
+        range: 7..=7,
+    };
+    assert!(matches!(bob.receive(), Some(Reply::Winner { .. })));
+    assert_eq!(bob.receive(), Some(round_two));
+
+    // Bob's miss in round 1 does not count in round 2.
+    bob.send(&Request::Guess(7)).unwrap();
+    assert_eq!(bob.receive(), Some(Reply::Correct(7)));
+    assert_eq!(
+        bob.receive(),
+        Some(Reply::Winner {
+            secret: 7,
+            attempts: 1,
+            name: "bob".to_string(),
+        })
+    );
+}
+
+#[test]
+fn the_last_round_is_won_only_once() {
+    let (addr, server) = start_server(ServerConfig {
+        range: 7..=7,
+        rounds: Some(1),
+        seed: None,
+    });
+    let mut alice = joined(addr, "alice");
+    let mut bob = joined(addr, "bob");
+
+    // Both guesses reach the server, but only the first one ends the game.
+    alice.send(&Request::Guess(7)).unwrap();
+    bob.send(&Request::Guess(7)).unwrap();
+    for client in [&mut alice, &mut bob] {
+        let replies: Vec<Reply> = std::iter::from_fn(|| client.receive()).collect();
+        let winners = replies
+            .iter()
+            .filter(|reply| matches!(reply, Reply::Winner { .. }))
+            .count();
+        assert_eq!(winners, 1, "{:?}", replies);
+        assert_eq!(replies.last(), Some(&Reply::Bye));
+    }
+    server.join().unwrap();
+}
+
+#[test]
+fn requests_out_of_turn_are_refused() {
+    let (addr, _server) = start_server(ServerConfig::default());
+    let mut early = Client::connect(addr).unwrap();
+    early.send(&Request::Guess(50)).unwrap();
+    assert!(matches!(early.receive(), Some(Reply::Error(_))));
+
+    let _alice = joined(addr, "alice");
+    assert!(matches!(early.join("alice").unwrap(), Reply::Error(_)));
+
+    let mut impostor = Client::connect(addr).unwrap();
+    impostor.send(&Request::Quit).unwrap();
+    assert_eq!(impostor.receive(), Some(Reply::Bye));
+}
+
+#[test]
+fn separate_processes_can_race() {
+    let binary = env!("CARGO_BIN_EXE_hello-rust");
+    let mut server = Command::new(binary)
+        .args([
+            "serve",
+            "--addr",
+            "127.0.0.1:0",
+            "--range",
+            "1..=1",
+            "--rounds",
+            "1",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut server_out = BufReader::new(server.stdout.take().unwrap());
+    let mut line = String::new();
+    server_out.read_line(&mut line).unwrap();
+    let addr = line
+        .trim()
+        .strip_prefix("Listening on ")
+        .unwrap()
+        .to_string();
+
+    let client = |name: &str| {
+        Command::new(binary)
+            .args(["connect", "--addr", &addr, "--name", name])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap()
+    };
+
+    // Bob joins and waits; Alice joins and guesses the only possible number.
+    let mut bob = client("bob");
+    let mut bob_out = BufReader::new(bob.stdout.take().unwrap());
+    line.clear();
+    bob_out.read_line(&mut line).unwrap();
+    assert!(line.starts_with("Joined round 1 as bob"), "{}", line);
+
+    let mut alice = client("alice");
+    alice.stdin.take().unwrap().write_all(b"1\n").unwrap();
+    let alice = alice.wait_with_output().unwrap();
+    assert!(alice.status.success());
+    let alice_out = String::from_utf8(alice.stdout).unwrap();
+    assert!(alice_out.contains("You win!"), "{}", alice_out);
+
+    // Bob still sees how the race ended once he stops playing.
+    drop(bob.stdin.take());
+    let mut rest = String::new();
+    std::io::Read::read_to_string(&mut bob_out, &mut rest).unwrap();
+    assert!(bob.wait().unwrap().success());
+    assert!(
+        rest.contains("alice won the round with 1 attempts. The secret number was 1."),
+        "{}",
+        rest
+    );
+    assert!(server.wait().unwrap().success());
+}
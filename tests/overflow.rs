@@ -0,0 +1,99 @@
+use std::process::Command;
+
+use hello_rust::literals::IntType;
+use hello_rust::overflow::{explore, infer_type, Cell, ExploreError, Op, Plain};
+
+fn value(text: &str) -> Cell {
+    Cell::Value(text.to_string())
+}
+
+#[test]
+fn addition_overflow_panics_only_in_debug_builds() {
+    let report = explore(IntType::I8, Op::Add, "127", Some("1")).unwrap();
+    assert_eq!(report.checked, Cell::Nothing);
+    assert_eq!(report.wrapping, value("-128"));
+    assert_eq!(report.saturating, value("127"));
+    assert_eq!(report.overflowing, value("-128"));
+    assert!(report.overflowed);
+    assert_eq!(
+        report.plain,
+        Some(Plain {
+            debug: Cell::Panics,
+            release: value("-128"),
+            panic: Some("attempt to add with overflow"),
+        })
+    );
+
+    let report = explore(IntType::U8, Op::Mul, "15", Some("17")).unwrap();
+    assert_eq!(report.checked, value("255"));
+    assert!(!report.overflowed);
+    assert_eq!(report.plain.unwrap().release, value("255"));
+}
+
+#[test]
+fn division_panics_in_every_build() {
+    let report = explore(IntType::I32, Op::Div, "-2147483648", Some("-1")).unwrap();
+    assert_eq!(report.checked, Cell::Nothing);
+    assert_eq!(report.wrapping, value("-2147483648"));
+    assert_eq!(report.saturating, value("2147483647"));
+    let plain = report.plain.unwrap();
+    assert_eq!((plain.debug, plain.release), (Cell::Panics, Cell::Panics));
+
+    let report = explore(IntType::U8, Op::Rem, "5", Some("0")).unwrap();
+    assert_eq!(report.checked, Cell::Nothing);
+    assert_eq!(report.wrapping, Cell::Panics);
+    // There is no saturating_rem.
+    assert_eq!(report.saturating, Cell::Missing);
+    assert_eq!(
+        report.plain.unwrap().panic,
+        Some("attempt to calculate the remainder with a divisor of zero")
+    );
+}
+
+#[test]
+fn negation_and_shifts() {
+    let report = explore(IntType::U8, Op::Neg, "1", None).unwrap();
+    assert_eq!(report.wrapping, value("255"));
+    assert_eq!(report.saturating, Cell::Missing);
+    assert_eq!(report.plain, None);
+    assert!(report.to_string().contains("does not compile for u8"));
+
+    // Shifting by the width of the type or more overflows, and the wrapping
+    // shift masks the amount rather than the result.
+    let report = explore(IntType::I32, Op::Shl, "1", Some("33")).unwrap();
+    assert_eq!(report.checked, Cell::Nothing);
+    assert_eq!(report.wrapping, value("2"));
+    assert!(report.overflowed);
+}
+
+#[test]
+fn operands_must_have_the_type() {
+    assert!(matches!(
+        explore(IntType::U8, Op::Add, "1u8", Some("1i8")),
+        Err(ExploreError::WrongType { .. })
+    ));
+    assert!(matches!(
+        explore(IntType::I8, Op::Add, "128", Some("1")),
+        Err(ExploreError::Literal(..))
+    ));
+    assert!(matches!(
+        explore(IntType::I8, Op::Add, "1", None),
+        Err(ExploreError::Operands { .. })
+    ));
+    assert_eq!(infer_type(Op::Add, "1", Some("2u16")), IntType::U16);
+    assert_eq!(infer_type(Op::Pow, "2", Some("8u32")), IntType::I32);
+    assert_eq!("<<".parse::<Op>(), Ok(Op::Shl));
+}
+
+#[test]
+fn the_cli_prints_json() {
+    let output = Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+        .args(["overflow", "mul", "200u8", "2", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        r#"{"type":"u8","operation":"mul","operands":[200,2],"checked":null,"wrapping":144,"saturating":255,"overflowing":{"value":144,"overflowed":true},"plain":{"debug":"panic","release":144,"panic_message":"attempt to multiply with overflow"}}"#
+    );
+}
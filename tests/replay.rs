@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use hello_rust::guessing::{
+    Difficulty, GameSettings, HintSettings, Mismatch, Outcome, ReplayError, Session,
+};
+use hello_rust::part02;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// A replay directory in the system temp directory that no other test uses.
+fn replay_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "hello-rust-replays-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&path);
+    path
+}
+
+// Every log in tests/replays is a recorded session, usually one a player
+// reported a bug with. The game must still answer each of them the same way.
+#[test]
+fn recorded_sessions_still_replay() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/replays");
+    let mut replayed = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let session = Session::load(&path).unwrap();
+        let game = session
+            .replay()
+            .unwrap_or_else(|mismatch| panic!("{}: {}", path.display(), mismatch));
+        assert!(game.is_over(), "{} does not finish", path.display());
+        replayed += 1;
+    }
+    assert!(replayed > 0);
+}
+
+#[test]
+fn played_games_are_recorded() {
+    let dir = replay_dir("played");
+    let settings = GameSettings {
+        difficulty: Difficulty::Custom {
+            range: 1..=3,
+            max_attempts: None,
+        },
+        reveal_secret: false,
+        score_file: None,
+        replay_dir: Some(dir.clone()),
+        hints: HintSettings::default(),
+    };
+    let mut input = &b"one\n1\n2\n3\n"[..];
+    let mut out = Vec::new();
+    part02::guess_game_v3(
+        &mut input,
+        &mut out,
+        &mut StdRng::seed_from_u64(8),
+        &settings,
+    )
+    .unwrap();
+
+    let logs: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(logs.len(), 1);
+    let session = Session::load(&logs[0]).unwrap();
+    assert_eq!(session.range, 1..=3);
+    assert_eq!(session.guesses[0].input, "one");
+    assert_eq!(session.guesses[0].outcome, Outcome::Invalid);
+    assert_eq!(session.guesses.last().unwrap().outcome, Outcome::Win);
+
+    let game = session.replay().unwrap();
+    assert!(game.is_won());
+    assert_eq!(session.to_string().parse::<Session>().unwrap(), session);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn changed_feedback_is_a_mismatch() {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/replays/win-after-invalid-input.replay");
+    let text = fs::read_to_string(path)
+        .unwrap()
+        .replace("too-big 62", "too-small 62");
+    let session: Session = text.parse().unwrap();
+    assert_eq!(
+        session.replay().unwrap_err(),
+        Mismatch {
+            line: 4,
+            input: "62".to_string(),
+            recorded: Outcome::TooSmall,
+            replayed: Outcome::TooBig,
+        }
+    );
+}
+
+#[test]
+fn broken_logs_name_the_line() {
+    let text = "hello-rust-replay 1\nseed 1\nrange 10 0\n";
+    match text.parse::<Session>() {
+        Err(ReplayError::Parse { line, .. }) => assert_eq!(line, 3),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
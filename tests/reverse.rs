@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+use hello_rust::guessing::{
+    play_reverse, Answer, Bound, Contradiction, ReverseGame, DEFAULT_RANGE,
+};
+
+// Answers the way a player thinking of `number` would.
+fn honest(number: u32, guess: u32) -> Answer {
+    match number.cmp(&guess) {
+        Ordering::Greater => Answer::Higher,
+        Ordering::Less => Answer::Lower,
+        Ordering::Equal => Answer::Correct,
+    }
+}
+
+#[test]
+fn finds_every_number_in_the_default_range() {
+    for number in DEFAULT_RANGE {
+        let mut game = ReverseGame::new(DEFAULT_RANGE);
+        while let Some(guess) = game.next_guess() {
+            game.answer(guess, honest(number, guess)).unwrap();
+        }
+        assert_eq!(game.found(), Some(number));
+        assert!(game.guesses() <= 7);
+    }
+}
+
+#[test]
+fn contradictions_name_both_answers() {
+    let mut game = ReverseGame::new(0..=100);
+    game.answer(50, Answer::Higher).unwrap();
+    game.answer(75, Answer::Lower).unwrap();
+    let contradiction = game.answer(51, Answer::Lower).unwrap_err();
+    assert_eq!(
+        contradiction,
+        Contradiction {
+            lower: Bound::Answer {
+                turn: 1,
+                guess: 50,
+                answer: Answer::Higher,
+            },
+            upper: Bound::Answer {
+                turn: 3,
+                guess: 51,
+                answer: Answer::Lower,
+            },
+        }
+    );
+    // The contradicting answer was not taken.
+    assert_eq!(game.candidates(), 51..=74);
+    assert_eq!(game.guesses(), 2);
+}
+
+#[test]
+fn answers_past_the_range_contradict_it() {
+    let mut game = ReverseGame::new(0..=u32::MAX);
+    let contradiction = game.answer(0, Answer::Lower).unwrap_err();
+    assert_eq!(contradiction.lower, Bound::RangeStart(0));
+    let contradiction = game.answer(u32::MAX, Answer::Higher).unwrap_err();
+    assert_eq!(contradiction.upper, Bound::RangeEnd(u32::MAX));
+}
+
+#[test]
+fn the_front_end_reports_the_contradiction() {
+    let mut out = Vec::new();
+    let mut input = &b"higher\nmaybe\nlower\nlower\n"[..];
+    play_reverse(&mut input, &mut out, 0..=3).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(
+        out,
+        "\
+Think of a number between 0 and 3, and I will guess it.
+Is it 1? (higher, lower or correct)
+Is it 2? (higher, lower or correct)
+'maybe' is not an answer, expected higher, lower or correct
+Is it 2? (higher, lower or correct)
+That cannot be right: your number cannot be both higher than 1 (your answer to guess 1) \
+and lower than 2 (your answer to guess 2).
+"
+    );
+}
@@ -0,0 +1,91 @@
+use hello_rust::rules::{Condition, Mode, RuleSet, RulesError};
+
+#[test]
+fn three_and_five_make_fizz_buzz() {
+    let labels: Vec<String> = RuleSet::divisors([(3, "Fizz"), (5, "Buzz")])
+        .run(1..=15)
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "1", "2", "Fizz", "4", "Buzz", "Fizz", "7", "8", "Fizz", "Buzz", "11", "Fizz", "13",
+            "14", "FizzBuzz"
+        ]
+    );
+    assert_eq!(RuleSet::default(), RuleSet::fizz_buzz());
+}
+
+#[test]
+fn priority_mode_is_an_if_else_chain() {
+    // part03::control_flow
+    let rules = RuleSet::new(Mode::Priority)
+        .with_rule(Condition::DivisibleBy(4), "divisible by 4")
+        .with_rule(Condition::DivisibleBy(3), "divisible by 3")
+        .with_otherwise("not divisible by 4, 3");
+    assert_eq!(rules.apply(6), "divisible by 3");
+    assert_eq!(rules.apply(12), "divisible by 4");
+    assert_eq!(rules.apply(7), "not divisible by 4, 3");
+
+    let loaded = RuleSet::load("tests/rules/control_flow.txt").unwrap();
+    assert_eq!(loaded.apply(6), "number is divisible by 3");
+    assert_eq!(loaded.apply(12), "number is divisible by 4");
+}
+
+#[test]
+fn ranges_and_predicates() {
+    let rules = RuleSet::new(Mode::Cumulative)
+        .with_rule(Condition::InRange(1..=9), "small ")
+        .with_rule(Condition::Odd, "odd ")
+        .with_rule(Condition::Square, "square ")
+        .with_otherwise("{n} is none of these");
+    assert_eq!(rules.apply(9), "small odd square ");
+    assert_eq!(rules.apply(16), "square ");
+    assert_eq!(rules.apply(10), "10 is none of these");
+    assert!(Condition::Even.holds(0));
+    assert!(Condition::Square.holds(0));
+    assert!(!Condition::Square.holds(u32::MAX));
+}
+
+#[test]
+fn rule_sets_read_back_what_they_show() {
+    let text = "
+        # Comments and blank lines are skipped.
+        mode: cumulative
+        div 3: Fizz
+        divisible by 5: Buzz
+        in 10..=20: !
+        otherwise: -{n}-
+    ";
+    let rules = RuleSet::parse(text).unwrap();
+    assert_eq!(rules.mode(), Mode::Cumulative);
+    assert_eq!(rules.rules().len(), 3);
+    assert_eq!(rules.apply(15), "FizzBuzz!");
+    assert_eq!(rules.apply(7), "-7-");
+    assert_eq!(
+        rules.to_string(),
+        "mode: cumulative\ndivisible by 3: Fizz\ndivisible by 5: Buzz\nin 10..=20: !\notherwise: -{n}-"
+    );
+    assert_eq!(RuleSet::parse(&rules.to_string()).unwrap(), rules);
+}
+
+#[test]
+fn bad_lines_are_reported_with_their_number() {
+    let error = |text: &str| match RuleSet::parse(text) {
+        Err(RulesError::Parse { line, reason }) => (line, reason),
+        other => panic!("expected a parse error, got {:?}", other),
+    };
+    assert_eq!(error("div 3: Fizz\n\nFizz").0, 3);
+    assert_eq!(error("mode: random").1, "unknown mode 'random', expected priority or cumulative");
+    assert!(error("divisible by 0: never").1.starts_with("unknown condition"));
+    assert!(error("in 5..=1: backwards").1.starts_with("unknown condition"));
+    assert!(matches!(
+        RuleSet::load("tests/rules/missing.txt"),
+        Err(RulesError::Io(..))
+    ));
+}
+
+#[test]
+#[should_panic(expected = "divisibility by 0")]
+fn a_divisor_of_zero_is_refused() {
+    RuleSet::divisors([(3, "Fizz"), (0, "Never")]);
+}
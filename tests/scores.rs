@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use hello_rust::duration::Span;
+use hello_rust::guessing::{
+    GuessCost, RangeBits, Score, ScoreTable, ScoringModel, MAX_SCORES_PER_RANGE,
+};
+
+// A score file in the system temp directory that no other test uses.
+fn score_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "hello-rust-{}-{}.tsv",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn missing_file_is_created_empty() {
+    let path = score_file("missing");
+    let table = ScoreTable::load(&path).unwrap();
+    assert!(table.is_empty());
+    assert!(path.exists());
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn corrupt_lines_are_skipped() {
+    let path = score_file("corrupt");
+    fs::write(
+        &path,
+        b"ann\t4\t1500\t0\t100\t1700000000\nnot a score\n\xff\xfe\nbob\tx\t1\t0\t100\t0\n",
+    )
+    .unwrap();
+
+    let table = ScoreTable::load(&path).unwrap();
+    assert_eq!(table.skipped_lines(), 3);
+    let top = table.top(&(0..=100), 10);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].name, "ann");
+    assert_eq!(top[0].elapsed, Duration::from_millis(1500));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn scores_are_ranked_and_kept_per_range() {
+    let path = score_file("ranked");
+    let mut table = ScoreTable::load(&path).unwrap();
+    table.add(Score::new("slow", 5, Duration::from_secs(30), 0..=100));
+    table.add(Score::new("other range", 1, Duration::from_secs(1), 0..=50));
+    assert_eq!(
+        table.add(Score::new("fast", 5, Duration::from_secs(10), 0..=100)),
+        Some(1)
+    );
+    for _ in 0..MAX_SCORES_PER_RANGE {
+        table.add(Score::new("best", 2, Duration::from_secs(1), 0..=100));
+    }
+    assert_eq!(
+        table.add(Score::new("late", 9, Duration::from_secs(1), 0..=100)),
+        None
+    );
+    table.save().unwrap();
+
+    let table = ScoreTable::load(&path).unwrap();
+    let top = table.top(&(0..=100), 100);
+    assert_eq!(top.len(), MAX_SCORES_PER_RANGE);
+    assert!(top.iter().all(|score| score.name == "best"));
+    assert_eq!(table.top(&(0..=50), 10)[0].name, "other range");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn hints_cost_points() {
+    let path = score_file("hints");
+    // A line from before hints were recorded.
+    fs::write(&path, "old\t4\t1500\t0\t100\t1700000000\n").unwrap();
+    let mut table = ScoreTable::load(&path).unwrap();
+    assert_eq!(table.top(&(0..=100), 1)[0].hints, 0);
+    let helped = Score::new("helped", 3, Duration::from_secs(1), 0..=100).with_hints(3);
+    assert_eq!(table.add(helped), Some(2));
+    table.save().unwrap();
+
+    let table = ScoreTable::load_with(&path, Box::new(RangeBits)).unwrap();
+    let top = table.top(&(0..=100), 10);
+    assert_eq!(top[0].name, "old");
+    assert_eq!(top[1].hints, 3);
+    // log2(101) is about 6.66 bits.
+    assert_eq!(table.scoring().points(top[0]), 266);
+    assert_eq!(GuessCost::default().points(top[1]), 550);
+    // Counts too large for a u32 sum, as a hand-edited file may have.
+    let absurd =
+        Score::new("absurd", u32::MAX, Duration::from_secs(1), 0..=100).with_hints(u32::MAX);
+    assert!(RangeBits.points(&absurd) < 0);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn ranking_another_way_loses_no_scores() {
+    let path = score_file("reranked");
+    // One score more than is kept, as an older or hand-edited file may have.
+    // The helped one is the best by guesses, but the worst by range bits.
+    let mut lines = "plain\t3\t1000\t0\t100\t1700000000\n".repeat(MAX_SCORES_PER_RANGE);
+    lines.push_str("helped\t1\t1000\t0\t100\t1700000000\t3\n");
+    fs::write(&path, lines).unwrap();
+
+    let mut table = ScoreTable::load_with(&path, Box::new(RangeBits)).unwrap();
+    assert_eq!(table.top(&(0..=100), 100).last().unwrap().name, "helped");
+    table.reset(Some(&(0..=50))).unwrap();
+
+    let table = ScoreTable::load(&path).unwrap();
+    assert_eq!(table.top(&(0..=100), 1)[0].name, "helped");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn scores_know_how_old_they_are() {
+    let mut score = Score::new("ann", 3, Duration::from_secs(5), 0..=100);
+    score.date = 1_700_000_000;
+    assert_eq!(score.age(1_700_090_000), "1d1h".parse().unwrap());
+    assert_eq!(score.age(1_600_000_000), Span::ZERO);
+}
@@ -0,0 +1,134 @@
+use hello_rust::bigint::BigUint;
+use hello_rust::sequences::{
+    collatz, factorials, fibonacci, primes, Sequence, SequenceError, Terms,
+};
+
+fn big(digits: &str) -> BigUint {
+    digits.parse().unwrap()
+}
+
+#[test]
+fn sequences_start_as_expected() {
+    let first = |terms: Box<dyn Terms<Item = u64>>| terms.take(10).collect::<Vec<u64>>();
+    assert_eq!(
+        first(Box::new(fibonacci())),
+        [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+    );
+    assert_eq!(
+        first(Box::new(factorials())),
+        [1, 1, 2, 6, 24, 120, 720, 5040, 40320, 362880]
+    );
+    assert_eq!(
+        first(Box::new(primes())),
+        [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+    );
+    assert_eq!(first(Box::new(collatz(6))), [6, 3, 10, 5, 16, 8, 4, 2, 1]);
+    assert_eq!(collatz(27u64).count(), 112);
+    assert_eq!(collatz(0u64).count(), 0);
+}
+
+#[test]
+fn fixed_width_terms_stop_before_overflowing() {
+    let mut terms = fibonacci::<u64>();
+    assert_eq!(terms.by_ref().last(), Some(12_200_160_415_121_876_738));
+    assert!(terms.overflowed());
+
+    let mut terms = factorials::<u32>();
+    assert_eq!(terms.by_ref().count(), 13); // 12! is the last to fit
+    assert!(terms.overflowed());
+
+    // u64::MAX is odd, and 3n + 1 does not fit.
+    let mut terms = collatz(u64::MAX);
+    assert_eq!(terms.by_ref().count(), 1);
+    assert!(terms.overflowed());
+
+    // Reaching 1 is the end of the sequence, not an overflow.
+    let mut terms = collatz(7u32);
+    assert_eq!(terms.by_ref().last(), Some(1));
+    assert!(!terms.overflowed());
+}
+
+#[test]
+fn big_terms_go_on() {
+    assert_eq!(
+        fibonacci::<BigUint>().nth(200).unwrap(),
+        big("280571172992510140037611932413038677189525")
+    );
+    assert_eq!(
+        factorials::<BigUint>().nth(30).unwrap(),
+        big("265252859812191058636308480000000")
+    );
+    assert_eq!(primes::<BigUint>().nth(99).unwrap(), BigUint::from(541u32));
+    // The same steps as with u64, where that does not overflow.
+    let start = big("18446744073709551615");
+    let terms: Vec<BigUint> = collatz(start.clone()).take(3).collect();
+    assert_eq!(
+        terms,
+        [
+            start,
+            big("55340232221128654846"),
+            big("27670116110564327423")
+        ]
+    );
+}
+
+#[test]
+fn big_numbers_do_arithmetic_by_hand() {
+    let max = BigUint::from(u64::MAX);
+    let one = BigUint::from(1u32);
+    assert_eq!((&max + &one).to_string(), "18446744073709551616");
+    assert_eq!(
+        (&max * &max).to_string(),
+        (u128::from(u64::MAX) * u128::from(u64::MAX)).to_string()
+    );
+    assert_eq!(
+        &big("1000000000000000000000") % &big("999999999999"),
+        big("1000000000")
+    );
+    assert_eq!(&big("1000000000000000000000") % &big("7"), big("6"));
+    assert_eq!(&BigUint::zero() % &big("7"), BigUint::zero());
+    assert_eq!(max.checked_rem(&BigUint::zero()), None);
+    assert_eq!(big("7").half(), big("3"));
+    assert_eq!(big("1_000_000_000").to_string(), "1000000000");
+    assert_eq!(BigUint::zero().to_string(), "0");
+    assert!(big("10") > big("9"));
+    assert!("-1".parse::<BigUint>().is_err());
+    assert!("12a".parse::<BigUint>().is_err());
+}
+
+#[test]
+fn terms_are_picked_by_index() {
+    assert_eq!(
+        Sequence::Fibonacci.terms(0u64, 10..=12),
+        Ok(vec![55, 89, 144])
+    );
+    // The 1st prime is 2.
+    assert_eq!(Sequence::Primes.terms(0u64, 1..=1), Ok(vec![2]));
+    assert!(matches!(
+        Sequence::Primes.terms(0u64, 0..=1),
+        Err(SequenceError::NoSuchIndex { index: 0, .. })
+    ));
+    assert_eq!(
+        Sequence::Fibonacci.terms(0u64, 90..=100),
+        Err(SequenceError::TooLarge {
+            sequence: Sequence::Fibonacci,
+            index: 94,
+            ty: "u64"
+        })
+    );
+    // A Collatz sequence gives the terms it has.
+    assert_eq!(Sequence::Collatz.terms(4u64, 0..=10), Ok(vec![4, 2, 1]));
+    assert!(Sequence::Collatz.terms(4u64, 3..=10).is_err());
+    // One at a time, the terms that fit come before the error.
+    let mut terms = Sequence::Fibonacci.indexed(0u64, 93..=100);
+    assert_eq!(terms.next(), Some(Ok(12200160415121876738)));
+    assert!(matches!(
+        terms.next(),
+        Some(Err(SequenceError::TooLarge { index: 94, .. }))
+    ));
+    assert_eq!(terms.next(), None);
+    assert_eq!(
+        Sequence::Factorial.indexed(0u64, 0..=u32::MAX).nth(3),
+        Some(Ok(6))
+    );
+}
@@ -0,0 +1,45 @@
+use std::cmp::Ordering;
+
+use hello_rust::guessing::{
+    simulate, strategy_by_name, BinarySearch, Difficulty, Strategy, STRATEGIES,
+};
+
+#[test]
+fn binary_search_halves_the_range() {
+    let mut bot = BinarySearch::new();
+    bot.start(0..=100);
+    assert_eq!(bot.next_guess(), 50);
+    bot.feedback(50, Ordering::Greater);
+    assert_eq!(bot.next_guess(), 24);
+    bot.feedback(24, Ordering::Less);
+    assert_eq!(bot.next_guess(), 37);
+}
+
+#[test]
+fn binary_search_wins_every_preset() {
+    let mut bot = BinarySearch::new();
+    for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        let report = simulate(&mut bot, &difficulty, 500, 1);
+        assert_eq!(report.losses, 0, "{}", report);
+        assert_eq!(report.wins(), 500);
+        assert!(report.max_attempts() <= difficulty.max_attempts());
+    }
+}
+
+#[test]
+fn simulations_are_reproducible() {
+    let difficulty = Difficulty::Custom {
+        range: 1..=300,
+        max_attempts: None,
+    };
+    for name in STRATEGIES {
+        let run = || {
+            let mut bot = strategy_by_name(name, 9).unwrap();
+            simulate(bot.as_mut(), &difficulty, 200, 3)
+        };
+        let report = run();
+        assert_eq!(report, run());
+        // Without an attempt limit every bot gets there eventually.
+        assert_eq!(report.wins(), 200, "{}", report);
+    }
+}
@@ -0,0 +1,106 @@
+use hello_rust::ownership::{gives_ownership, takes_and_gives_back, takes_ownership};
+use hello_rust::traced::{Event, Log};
+
+#[test]
+fn a_moved_value_is_dropped_once() {
+    // The double free chapter 04 warns about: s1 and s2 would both free
+    // the same string, but after the move only s2 drops it.
+    let log = Log::new();
+    {
+        let s1 = log.track("s1", String::from("Hello"));
+        let s2 = s1.hand_to("s2");
+        assert_eq!(*s2, "Hello");
+    }
+    assert_eq!(
+        log.events(),
+        [
+            Event::Created { id: 1, owner: "s1" },
+            Event::Moved {
+                id: 1,
+                from: "s1",
+                to: "s2"
+            },
+            Event::Dropped { id: 1, owner: "s2" },
+        ]
+    );
+}
+
+#[test]
+fn takes_ownership_drops_before_returning() {
+    let log = Log::new();
+    let message = log.track("message", String::from("Hello"));
+    let mut out = Vec::new();
+    takes_ownership(&mut out, message.hand_to("takes_ownership")).unwrap();
+    // Dropped already, while the caller is still running.
+    assert_eq!(log.drops(), [1]);
+    assert_eq!(
+        log.events().last(),
+        Some(&Event::Dropped {
+            id: 1,
+            owner: "takes_ownership"
+        })
+    );
+    assert_eq!(out, b"Hello\n");
+}
+
+#[test]
+fn returned_values_are_dropped_by_the_caller_in_reverse_order() {
+    let log = Log::new();
+    {
+        let s1 = gives_ownership(&log).hand_to("s1");
+        let s2 = log.track("s2", String::from("Hello"));
+        let s3 = takes_and_gives_back(s2.hand_to("takes_and_gives_back")).hand_to("s3");
+        assert!(log.drops().is_empty());
+        assert_eq!((s1.id(), s3.id()), (1, 2));
+    }
+    // s3 was declared last, so it goes first; s2 was moved into it.
+    assert_eq!(log.drops(), [2, 1]);
+    let dropped: Vec<String> = log
+        .events()
+        .iter()
+        .filter(|event| matches!(event, Event::Dropped { .. }))
+        .map(|event| event.to_string())
+        .collect();
+    assert_eq!(dropped, ["#2 dropped by s3", "#1 dropped by s1"]);
+}
+
+#[test]
+fn clones_have_ids_of_their_own() {
+    let log = Log::new();
+    {
+        let s1 = log.track("s1", String::from("Hello"));
+        let s2 = s1.clone_as("s2");
+        let s3 = s2.clone();
+        assert_eq!((s2.id(), s3.id()), (2, 3));
+        assert_eq!(s3.owner(), "s2");
+        drop(s1);
+        assert_eq!(log.drops(), [1]);
+    }
+    assert_eq!(log.drops(), [1, 3, 2]);
+    assert_eq!(
+        log.events()[1],
+        Event::Cloned {
+            id: 2,
+            from: 1,
+            owner: "s2"
+        }
+    );
+}
+
+#[test]
+fn the_chapter_prints_the_events() {
+    let mut out = Vec::new();
+    hello_rust::part04::run(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(
+        text.contains("#1 moved from s1 to s2\n#1 dropped by s2\n"),
+        "{}",
+        text
+    );
+    assert!(text.contains("#1 dropped by takes_ownership\n"), "{}", text);
+    assert!(
+        text.ends_with("#2 dropped by s3\n#1 dropped by s1\n"),
+        "{}",
+        text
+    );
+}
@@ -0,0 +1,124 @@
+use std::process::Command;
+
+use hello_rust::duration::Span;
+use hello_rust::units::{
+    Celsius, Dimension, Fahrenheit, Feet, Kelvin, Metres, Quantity, Seconds, Unit, UnitsError,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn temperatures_convert_between_scales() {
+    assert!(close(Fahrenheit::from(Celsius(100.0)).0, 212.0));
+    assert!(close(Celsius::from(Fahrenheit(-40.0)).0, -40.0));
+    assert!(close(Kelvin::from(Celsius(0.0)).0, 273.15));
+    assert!(close(Fahrenheit::from(Kelvin(0.0)).0, -459.67));
+    // There and back again.
+    let body = Fahrenheit(98.6);
+    assert!(close(Fahrenheit::from(Kelvin::from(body)).0, body.0));
+}
+
+#[test]
+fn lengths_and_times_add_up() {
+    assert!(close(Metres::from(Feet(1.0)).0, 0.3048));
+    assert!(close(Feet::from(Metres(0.3048) * 10.0).0, 10.0));
+    assert_eq!(Metres(1.5) + Metres(2.0) - Metres(0.5), Metres(3.0));
+    assert_eq!(Seconds::from(Span::from_mins(90)), Seconds(5400.0));
+    assert_eq!(Seconds(1.0).to_string(), "1 s");
+}
+
+#[test]
+fn quantities_are_parsed_with_or_without_a_space() {
+    let body: Quantity = "98.6F".parse().unwrap();
+    assert_eq!(
+        body,
+        Quantity {
+            value: 98.6,
+            unit: Unit::Fahrenheit
+        }
+    );
+    let run: Quantity = "5 km".parse().unwrap();
+    assert_eq!(run.unit, Unit::Kilometres);
+    assert_eq!(run.to(Unit::Metres).unwrap().value, 5000.0);
+    assert_eq!("-40 °C".parse::<Quantity>().unwrap().unit, Unit::Celsius);
+    assert_eq!("3 feet".parse::<Quantity>().unwrap().unit, Unit::Feet);
+
+    assert_eq!(
+        "12".parse::<Quantity>(),
+        Err(UnitsError::MissingUnit("12".to_string()))
+    );
+    assert_eq!(
+        "1.2.3 m".parse::<Quantity>(),
+        Err(UnitsError::InvalidNumber("1.2.3".to_string()))
+    );
+    assert_eq!(
+        "5 parsecs".parse::<Quantity>(),
+        Err(UnitsError::UnknownUnit("parsecs".to_string()))
+    );
+    assert_eq!(
+        "-300C".parse::<Quantity>(),
+        Err(UnitsError::BelowAbsoluteZero(-300.0, Unit::Celsius))
+    );
+    // Too big for an f64, so it would parse as infinity.
+    let huge = format!("-1{} m", "0".repeat(400));
+    assert!(matches!(
+        huge.parse::<Quantity>(),
+        Err(UnitsError::InvalidNumber(_))
+    ));
+    assert!(Quantity::new(f64::NAN, Unit::Metres).is_err());
+    // Converting can still overflow, and keeps the sign when it does.
+    let far = Quantity::new(-1e308, Unit::Kilometres).unwrap();
+    assert_eq!(far.to(Unit::Centimetres).unwrap().to_string(), "-inf cm");
+}
+
+#[test]
+fn only_units_of_the_same_dimension_convert() {
+    let body: Quantity = "98.6F".parse().unwrap();
+    let shown: Vec<String> = body.conversions().iter().map(|q| q.to_string()).collect();
+    assert_eq!(shown, ["98.6 °F", "37 °C", "310.15 K"]);
+    assert_eq!(
+        body.to(Unit::Metres),
+        Err(UnitsError::Mismatch {
+            from: Dimension::Temperature,
+            to: Dimension::Length,
+        })
+    );
+    let mile: Quantity = "1 mi".parse().unwrap();
+    assert!(close(mile.to(Unit::Kilometres).unwrap().value, 1.609344));
+}
+
+#[test]
+fn the_cli_converts_into_every_unit_or_just_one() {
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hello-rust"))
+            .arg("units")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["98.6F"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "98.6 °F\n= 37 °C\n= 310.15 K\n"
+    );
+
+    let output = run(&["5", "km", "--to", "mi"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "5 km\n= 3.10686 mi\n"
+    );
+
+    let output = run(&["90s", "--to", "ft"]);
+    assert!(!output.status.success());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        error.contains("cannot convert a time into a length"),
+        "{}",
+        error
+    );
+}